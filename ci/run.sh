@@ -16,6 +16,12 @@ if [ "$TARGET" = "x86_64-unknown-linux-musl" ]; then
   features="--features static-ssl"
 fi
 
+# The http3 image provides ngtcp2 and nghttp3, which are only needed there.
+if [ -n "$HTTP3" ]; then
+    cargo test --target $TARGET --features static-curl,http2,http3 $features
+    exit 0
+fi
+
 cargo test --target $TARGET --no-run $features
 # First test with no extra protocols enabled.
 cargo test --target $TARGET --no-run --features static-curl $features
@@ -33,19 +39,26 @@ then
     cargo test --target $TARGET --no-run --features rustls,static-curl $features
 fi
 # Then with all extra protocols enabled.
-cargo test --target $TARGET --no-run --features static-curl,protocol-ftp,ntlm $features
+cargo test --target $TARGET --no-run --features static-curl,protocol-ftp,protocol-imap,protocol-pop3,protocol-smtp,ntlm,ssh,brotli,zstd,serde,ws_7_86_0,header_7_83_0 $features
 if [ -z "$NO_RUN" ]; then
     cargo test --target $TARGET $features
     cargo test --target $TARGET --features static-curl $features
     cargo test --target $TARGET --features static-curl,protocol-ftp $features
+    cargo test --target $TARGET --features static-curl,protocol-smtp $features
     cargo test --target $TARGET --features static-curl,http2 $features
+    cargo test --target $TARGET --features static-curl,ssh $features
+    cargo test --target $TARGET --features static-curl,brotli,zstd $features
+    cargo test --target $TARGET --features static-curl,tokio $features
+    cargo test --target $TARGET --features static-curl,url_7_62_0 $features
+    cargo test --target $TARGET --features static-curl,ws_7_86_0 $features
+    cargo test --target $TARGET --features static-curl,header_7_83_0 $features
 
     # Note that `-Clink-dead-code` is passed here to suppress `--gc-sections` to
     # help confirm that we're compiling everything necessary for curl itself.
     RUSTFLAGS=-Clink-dead-code \
     cargo run --manifest-path systest/Cargo.toml --target $TARGET $features
     RUSTFLAGS=-Clink-dead-code \
-    cargo run --manifest-path systest/Cargo.toml --target $TARGET --features curl-sys/static-curl,curl-sys/protocol-ftp $features
+    cargo run --manifest-path systest/Cargo.toml --target $TARGET --features curl-sys/static-curl,curl-sys/protocol-ftp,curl-sys/protocol-smtp $features
 
     cargo doc --no-deps --target $TARGET $features
     cargo doc --no-deps -p curl-sys --target $TARGET $features
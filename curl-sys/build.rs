@@ -8,9 +8,13 @@ fn main() {
     println!(
         "cargo:rustc-check-cfg=cfg(\
             libcurl_vendored,\
+            link_brotli,\
+            link_cares,\
             link_libnghttp2,\
+            link_libssh2,\
             link_libz,\
             link_openssl,\
+            link_zstd,\
         )"
     );
     let target = env::var("TARGET").unwrap();
@@ -31,7 +35,13 @@ fn main() {
     if !cfg!(feature = "static-curl") {
         // OSX ships libcurl by default, so we just use that version
         // so long as it has the right features enabled.
-        if target.contains("apple") && (!cfg!(feature = "http2") || curl_config_reports_http2()) {
+        if target.contains("apple")
+            && (!cfg!(feature = "http2") || curl_config_reports_feature("HTTP2"))
+            && (!cfg!(feature = "http3") || curl_config_reports_feature("HTTP3"))
+            && (!cfg!(feature = "ssh") || curl_config_reports_ssh())
+            && (!cfg!(feature = "brotli") || curl_config_reports_feature("brotli"))
+            && (!cfg!(feature = "zstd") || curl_config_reports_feature("zstd"))
+        {
             return println!("cargo:rustc-flags=-l curl");
         }
 
@@ -108,13 +118,10 @@ fn main() {
         .define("BUILDING_LIBCURL", None)
         .define("CURL_DISABLE_DICT", None)
         .define("CURL_DISABLE_GOPHER", None)
-        .define("CURL_DISABLE_IMAP", None)
         .define("CURL_DISABLE_LDAP", None)
         .define("CURL_DISABLE_LDAPS", None)
-        .define("CURL_DISABLE_POP3", None)
         .define("CURL_DISABLE_RTSP", None)
         .define("CURL_DISABLE_SMB", None)
-        .define("CURL_DISABLE_SMTP", None)
         .define("CURL_DISABLE_TELNET", None)
         .define("CURL_DISABLE_TFTP", None)
         .define("CURL_STATICLIB", None)
@@ -128,10 +135,8 @@ fn main() {
         .define("HAVE_BOOL_T", None)
         .define("HAVE_STDBOOL_H", None)
         .define("HAVE_STDINT_H", None)
-        .define("USE_RESOLV_THREADED", None)
         .file("curl/lib/altsvc.c")
         .file("curl/lib/asyn-base.c")
-        .file("curl/lib/asyn-thrdd.c")
         .file("curl/lib/bufq.c")
         .file("curl/lib/bufref.c")
         .file("curl/lib/cf-dns.c")
@@ -278,12 +283,40 @@ fn main() {
     if cfg!(feature = "protocol-ftp") {
         cfg.file("curl/lib/curl_fnmatch.c")
             .file("curl/lib/ftp.c")
-            .file("curl/lib/ftplistparser.c")
-            .file("curl/lib/pingpong.c");
+            .file("curl/lib/ftplistparser.c");
     } else {
         cfg.define("CURL_DISABLE_FTP", None);
     }
 
+    if !cfg!(feature = "protocol-imap") {
+        cfg.define("CURL_DISABLE_IMAP", None);
+    }
+    if !cfg!(feature = "protocol-pop3") {
+        cfg.define("CURL_DISABLE_POP3", None);
+    }
+    if !cfg!(feature = "protocol-smtp") {
+        cfg.define("CURL_DISABLE_SMTP", None);
+    }
+    if cfg!(any(
+        feature = "protocol-imap",
+        feature = "protocol-pop3",
+        feature = "protocol-smtp"
+    )) {
+        cfg.file("curl/lib/curl_sasl.c")
+            .file("curl/lib/vauth/cleartext.c")
+            .file("curl/lib/vauth/cram.c")
+            .file("curl/lib/vauth/oauth2.c");
+    }
+
+    if cfg!(any(
+        feature = "protocol-ftp",
+        feature = "protocol-imap",
+        feature = "protocol-pop3",
+        feature = "protocol-smtp"
+    )) {
+        cfg.file("curl/lib/pingpong.c");
+    }
+
     if cfg!(feature = "http2") {
         cfg.define("USE_NGHTTP2", None)
             .define("NGHTTP2_STATICLIB", None)
@@ -297,11 +330,60 @@ fn main() {
         }
     }
 
+    if cfg!(feature = "ssh") {
+        cfg.define("USE_LIBSSH2", None)
+            .file("curl/lib/vssh/libssh2.c");
+
+        println!("cargo:rustc-cfg=link_libssh2");
+        if let Some(path) = env::var_os("DEP_SSH2_INCLUDE") {
+            cfg.include(path);
+        }
+    }
+
     println!("cargo:rustc-cfg=link_libz");
     if let Some(path) = env::var_os("DEP_Z_INCLUDE") {
         cfg.include(path);
     }
 
+    if cfg!(feature = "ares") {
+        cfg.define("USE_ARES", None)
+            .define("USE_RESOLV_ARES", None)
+            // c-ares-sys builds a static library.
+            .define("CARES_STATICLIB", None)
+            .file("curl/lib/asyn-ares.c");
+
+        println!("cargo:rustc-cfg=link_cares");
+        if let Some(path) = env::var_os("DEP_CARES_INCLUDE") {
+            cfg.include(path);
+        }
+    } else {
+        cfg.define("USE_RESOLV_THREADED", None)
+            .file("curl/lib/asyn-thrdd.c");
+    }
+
+    // brotlic-sys builds brotli 1.1, as libcurl needs a decoder newer than
+    // the 0.6 that brotli-sys bundles.
+    if cfg!(feature = "brotli") {
+        cfg.define("HAVE_BROTLI", None);
+
+        println!("cargo:rustc-cfg=link_brotli");
+        if let Some(path) = env::var_os("DEP_BROTLI_INCLUDE") {
+            cfg.include(path);
+        }
+    }
+
+    if cfg!(feature = "zstd") {
+        cfg.define("HAVE_ZSTD", None);
+
+        println!("cargo:rustc-cfg=link_zstd");
+        // zstd-sys separates multiple include directories with `;`.
+        if let Ok(paths) = env::var("DEP_ZSTD_INCLUDE") {
+            for path in paths.split(';').filter(|p| !p.is_empty()) {
+                cfg.include(path);
+            }
+        }
+    }
+
     if cfg!(feature = "spnego") {
         cfg.define("USE_SPNEGO", None)
             .file("curl/lib/http_negotiate.c")
@@ -362,6 +444,30 @@ fn main() {
         }
     }
 
+    // Unlike nghttp2 there are no crates vendoring ngtcp2 and nghttp3, so
+    // HTTP/3 links the static libraries found through pkg-config. ngtcp2 must
+    // be built with `ngtcp2_crypto_ossl`, which needs OpenSSL 3.5 or later.
+    if cfg!(feature = "http3") {
+        if windows || cfg!(feature = "rustls") || !cfg!(feature = "ssl") {
+            println!(
+                "cargo:warning=HTTP/3 is only supported with OpenSSL, ignoring the http3 feature."
+            );
+        } else {
+            cfg.define("USE_NGTCP2", None)
+                .define("USE_NGHTTP3", None)
+                .define("OPENSSL_QUIC_API2", None);
+            for name in ["libngtcp2", "libngtcp2_crypto_ossl", "libnghttp3"] {
+                let lib = pkg_config::Config::new()
+                    .statik(true)
+                    .probe(name)
+                    .unwrap_or_else(|e| panic!("failed to find {} for http3: {}", name, e));
+                for path in lib.include_paths.iter() {
+                    cfg.include(path);
+                }
+            }
+        }
+    }
+
     // Configure platform-specific details.
     if windows {
         cfg.define("WIN32", None)
@@ -557,7 +663,21 @@ fn try_pkg_config() -> bool {
 
     // Not all system builds of libcurl have http2 features enabled, so if we've
     // got a http2-requested build then we may fall back to a build from source.
-    if cfg!(feature = "http2") && !curl_config_reports_http2() {
+    if cfg!(feature = "http2") && !curl_config_reports_feature("HTTP2") {
+        return false;
+    }
+    if cfg!(feature = "http3") && !curl_config_reports_feature("HTTP3") {
+        return false;
+    }
+    if cfg!(feature = "brotli") && !curl_config_reports_feature("brotli") {
+        return false;
+    }
+    if cfg!(feature = "zstd") && !curl_config_reports_feature("zstd") {
+        return false;
+    }
+
+    // Likewise SFTP/SCP is frequently missing from system builds.
+    if cfg!(feature = "ssh") && !curl_config_reports_ssh() {
         return false;
     }
 
@@ -570,7 +690,7 @@ fn try_pkg_config() -> bool {
     true
 }
 
-fn curl_config_reports_http2() -> bool {
+fn curl_config_reports_feature(feature: &str) -> bool {
     let output = Command::new("curl-config").arg("--features").output();
     let output = match output {
         Ok(out) => out,
@@ -584,9 +704,35 @@ fn curl_config_reports_http2() -> bool {
         return false;
     }
     let stdout = String::from_utf8_lossy(&output.stdout);
-    if !stdout.contains("HTTP2") {
+    if !stdout.lines().any(|f| f == feature) {
+        println!(
+            "failed to find {} feature enabled in pkg-config-found \
+             libcurl, building from source",
+            feature
+        );
+        return false;
+    }
+
+    true
+}
+
+fn curl_config_reports_ssh() -> bool {
+    let output = Command::new("curl-config").arg("--protocols").output();
+    let output = match output {
+        Ok(out) => out,
+        Err(e) => {
+            println!("failed to run curl-config ({}), building from source", e);
+            return false;
+        }
+    };
+    if !output.status.success() {
+        println!("curl-config failed: {}", output.status);
+        return false;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.lines().any(|p| p == "SFTP") {
         println!(
-            "failed to find http-2 feature enabled in pkg-config-found \
+            "failed to find sftp protocol enabled in pkg-config-found \
              libcurl, building from source"
         );
         return false;
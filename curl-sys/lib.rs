@@ -2,14 +2,22 @@
 #![doc(html_root_url = "https://docs.rs/curl-sys/0.4")]
 
 // These `extern crate` are required for conditional linkages of curl.
+#[cfg(link_brotli)]
+extern crate brotlic_sys;
+#[cfg(link_cares)]
+extern crate c_ares_sys;
 #[cfg(link_libnghttp2)]
 extern crate libnghttp2_sys;
+#[cfg(link_libssh2)]
+extern crate libssh2_sys;
 #[cfg(link_libz)]
 extern crate libz_sys;
 #[cfg(link_openssl)]
 extern crate openssl_sys;
 #[cfg(feature = "rustls")]
 extern crate rustls_ffi;
+#[cfg(link_zstd)]
+extern crate zstd_sys;
 
 use libc::c_ulong;
 use libc::{c_char, c_double, c_int, c_long, c_short, c_uint, c_void, size_t, time_t};
@@ -75,11 +83,8 @@ pub enum curl_httppost {
 
 pub type curl_progress_callback =
     extern "C" fn(*mut c_void, c_double, c_double, c_double, c_double) -> c_int;
-// pub type curl_xferinfo_callback = extern fn(*mut c_void,
-//                                             curl_off_t,
-//                                             curl_off_t,
-//                                             curl_off_t,
-//                                             curl_off_t) -> c_int;
+pub type curl_xferinfo_callback =
+    extern "C" fn(*mut c_void, curl_off_t, curl_off_t, curl_off_t, curl_off_t) -> c_int;
 
 pub const CURL_WRITEFUNC_PAUSE: size_t = 0x10000001;
 
@@ -161,12 +166,46 @@ pub const CURL_READFUNC_ABORT: size_t = 0x10000000;
 pub const CURL_READFUNC_PAUSE: size_t = 0x10000001;
 pub type curl_read_callback = extern "C" fn(*mut c_char, size_t, size_t, *mut c_void) -> size_t;
 
-// pub const CURL_SOCKOPT_OK: c_int = 0;
-// pub const CURL_SOCKOPT_ERROR: c_int = 1;
-// pub const CURL_SOCKOPT_ALREADY_CONNECTED: c_int = 2;
-// pub type curl_sockopt_callback = extern fn(*mut c_void,
-//                                            curl_socket_t,
-//                                            curlsocktype) -> c_int;
+#[repr(C)]
+pub struct curl_hstsentry {
+    pub name: *mut c_char,
+    pub namelen: size_t,
+    // `unsigned int includeSubDomains:1` in C, which MSVC stores in a whole
+    // `unsigned int` and other compilers in a single byte.
+    #[cfg(not(target_env = "msvc"))]
+    pub includeSubDomains: u8,
+    #[cfg(target_env = "msvc")]
+    pub includeSubDomains: c_uint,
+    pub expire: [c_char; 18],
+}
+
+#[repr(C)]
+pub struct curl_index {
+    pub index: size_t,
+    pub total: size_t,
+}
+
+pub type CURLSTScode = __enum_ty;
+pub const CURLSTS_OK: CURLSTScode = 0;
+pub const CURLSTS_DONE: CURLSTScode = 1;
+pub const CURLSTS_FAIL: CURLSTScode = 2;
+pub type curl_hstsread_callback =
+    extern "C" fn(*mut CURL, *mut curl_hstsentry, *mut c_void) -> CURLSTScode;
+pub type curl_hstswrite_callback =
+    extern "C" fn(*mut CURL, *mut curl_hstsentry, *mut curl_index, *mut c_void) -> CURLSTScode;
+
+pub const CURLHSTS_ENABLE: c_long = 1 << 0;
+pub const CURLHSTS_READONLYFILE: c_long = 1 << 1;
+
+pub const CURLALTSVC_READONLYFILE: c_long = 1 << 2;
+pub const CURLALTSVC_H1: c_long = 1 << 3;
+pub const CURLALTSVC_H2: c_long = 1 << 4;
+pub const CURLALTSVC_H3: c_long = 1 << 5;
+
+pub const CURL_SOCKOPT_OK: c_int = 0;
+pub const CURL_SOCKOPT_ERROR: c_int = 1;
+pub const CURL_SOCKOPT_ALREADY_CONNECTED: c_int = 2;
+pub type curl_sockopt_callback = extern "C" fn(*mut c_void, curl_socket_t, curlsocktype) -> c_int;
 
 pub type curlioerr = __enum_ty;
 pub const CURLIOE_OK: curlioerr = 0;
@@ -315,48 +354,54 @@ pub const CURLAUTH_AWS_SIGV4: c_ulong = 1 << 7;
 pub const CURLAUTH_ANY: c_ulong = (!CURLAUTH_DIGEST_IE) & 0xffffffff;
 pub const CURLAUTH_ANYSAFE: c_ulong = (!(CURLAUTH_BASIC | CURLAUTH_DIGEST_IE)) & 0xffffffff;
 
-// pub const CURLSSH_AUTH_ANY: c_ulong = !0;
-// pub const CURLSSH_AUTH_NONE: c_ulong = 0;
-// pub const CURLSSH_AUTH_PUBLICKEY: c_ulong = 1 << 0;
-// pub const CURLSSH_AUTH_PASSWORD: c_ulong = 1 << 1;
-// pub const CURLSSH_AUTH_HOST: c_ulong = 1 << 2;
-// pub const CURLSSH_AUTH_KEYBOARD: c_ulong = 1 << 3;
-// pub const CURLSSH_AUTH_AGENT: c_ulong = 1 << 4;
-// pub const CURLSSH_AUTH_DEFAULT: c_ulong = CURLSSH_AUTH_ANY;
+pub const CURLSSH_AUTH_ANY: c_ulong = 0xffffffff;
+pub const CURLSSH_AUTH_NONE: c_ulong = 0;
+pub const CURLSSH_AUTH_PUBLICKEY: c_ulong = 1 << 0;
+pub const CURLSSH_AUTH_PASSWORD: c_ulong = 1 << 1;
+pub const CURLSSH_AUTH_HOST: c_ulong = 1 << 2;
+pub const CURLSSH_AUTH_KEYBOARD: c_ulong = 1 << 3;
+pub const CURLSSH_AUTH_AGENT: c_ulong = 1 << 4;
+pub const CURLSSH_AUTH_GSSAPI: c_ulong = 1 << 5;
+pub const CURLSSH_AUTH_DEFAULT: c_ulong = CURLSSH_AUTH_ANY;
 
 pub const CURLGSSAPI_DELEGATION_NONE: c_ulong = 0;
 pub const CURLGSSAPI_DELEGATION_POLICY_FLAG: c_ulong = 1 << 0;
 pub const CURLGSSAPI_DELEGATION_FLAG: c_ulong = 1 << 1;
 
-// pub type curl_khtype = __enum_ty;
-// pub const CURLKHTYPE_UNKNOWN: curl_khtype = 0;
-// pub const CURLKHTYPE_RSA1: curl_khtype = 1;
-// pub const CURLKHTYPE_RSA: curl_khtype = 2;
-// pub const CURLKHTYPE_DSS: curl_khtype = 3;
+pub type curl_khtype = __enum_ty;
+pub const CURLKHTYPE_UNKNOWN: curl_khtype = 0;
+pub const CURLKHTYPE_RSA1: curl_khtype = 1;
+pub const CURLKHTYPE_RSA: curl_khtype = 2;
+pub const CURLKHTYPE_DSS: curl_khtype = 3;
+pub const CURLKHTYPE_ECDSA: curl_khtype = 4;
+pub const CURLKHTYPE_ED25519: curl_khtype = 5;
 
-// #[repr(C)]
-// pub struct curl_khkey {
-//     pub key: *const c_char,
-//     pub len: size_t,
-//     pub keytype: curl_khtype,
-// }
+#[repr(C)]
+pub struct curl_khkey {
+    pub key: *const c_char,
+    pub len: size_t,
+    pub keytype: curl_khtype,
+}
 
-// pub type curl_khstat = __enum_ty;
-// pub const CURLKHSTAT_FINE_ADD_TO_FILE: curl_khstat = 0;
-// pub const CURLKHSTAT_FINE: curl_khstat = 1;
-// pub const CURLKHSTAT_REJECT: curl_khstat = 2;
-// pub const CURLKHSTAT_DEFER: curl_khstat = 3;
-//
-// pub type curl_khmatch = __enum_ty;
-// pub const CURLKHMATCH_OK: curl_khmatch = 0;
-// pub const CURLKHMATCH_MISMATCH: curl_khmatch = 1;
-// pub const CURLKHMATCH_MISSING: curl_khmatch = 2;
-
-// pub type curl_sshkeycallback = extern fn(*mut CURL,
-//                                          *const curl_khkey,
-//                                          *const curl_khkey,
-//                                          curl_khmatch,
-//                                          *mut c_void) -> c_int;
+pub type curl_khstat = __enum_ty;
+pub const CURLKHSTAT_FINE_ADD_TO_FILE: curl_khstat = 0;
+pub const CURLKHSTAT_FINE: curl_khstat = 1;
+pub const CURLKHSTAT_REJECT: curl_khstat = 2;
+pub const CURLKHSTAT_DEFER: curl_khstat = 3;
+pub const CURLKHSTAT_FINE_REPLACE: curl_khstat = 4;
+
+pub type curl_khmatch = __enum_ty;
+pub const CURLKHMATCH_OK: curl_khmatch = 0;
+pub const CURLKHMATCH_MISMATCH: curl_khmatch = 1;
+pub const CURLKHMATCH_MISSING: curl_khmatch = 2;
+
+pub type curl_sshkeycallback = extern "C" fn(
+    *mut CURL,
+    *const curl_khkey,
+    *const curl_khkey,
+    curl_khmatch,
+    *mut c_void,
+) -> c_int;
 
 pub const CURL_NETRC_IGNORED: c_ulong = 0;
 pub const CURL_NETRC_OPTIONAL: c_ulong = 1;
@@ -368,6 +413,15 @@ pub const CURLUSESSL_TRY: curl_usessl = 1;
 pub const CURLUSESSL_CONTROL: curl_usessl = 2;
 pub const CURLUSESSL_ALL: curl_usessl = 3;
 
+pub const CURLFTP_CREATE_DIR_NONE: c_long = 0;
+pub const CURLFTP_CREATE_DIR: c_long = 1;
+pub const CURLFTP_CREATE_DIR_RETRY: c_long = 2;
+
+pub const CURLFTPMETHOD_DEFAULT: c_long = 0;
+pub const CURLFTPMETHOD_MULTICWD: c_long = 1;
+pub const CURLFTPMETHOD_NOCWD: c_long = 2;
+pub const CURLFTPMETHOD_SINGLECWD: c_long = 3;
+
 pub const CURLPROTO_HTTP: c_int = 1 << 0;
 pub const CURLPROTO_HTTPS: c_int = 1 << 1;
 pub const CURLPROTO_FILE: c_int = 1 << 10;
@@ -431,6 +485,7 @@ pub const CURLOPT_TRANSFERTEXT: CURLoption = CURLOPTTYPE_LONG + 53;
 pub const CURLOPT_PUT: CURLoption = CURLOPTTYPE_LONG + 54;
 pub const CURLOPT_PROGRESSFUNCTION: CURLoption = CURLOPTTYPE_FUNCTIONPOINT + 56;
 pub const CURLOPT_PROGRESSDATA: CURLoption = CURLOPTTYPE_OBJECTPOINT + 57;
+pub const CURLOPT_XFERINFODATA: CURLoption = CURLOPT_PROGRESSDATA;
 pub const CURLOPT_AUTOREFERER: CURLoption = CURLOPTTYPE_LONG + 58;
 pub const CURLOPT_PROXYPORT: CURLoption = CURLOPTTYPE_LONG + 59;
 pub const CURLOPT_POSTFIELDSIZE: CURLoption = CURLOPTTYPE_LONG + 60;
@@ -579,15 +634,17 @@ pub const CURLOPT_TCP_KEEPALIVE: CURLoption = CURLOPTTYPE_LONG + 213;
 pub const CURLOPT_TCP_KEEPIDLE: CURLoption = CURLOPTTYPE_LONG + 214;
 pub const CURLOPT_TCP_KEEPINTVL: CURLoption = CURLOPTTYPE_LONG + 215;
 pub const CURLOPT_SSL_OPTIONS: CURLoption = CURLOPTTYPE_LONG + 216;
-// pub const CURLOPT_MAIL_AUTH: CURLoption = CURLOPTTYPE_OBJECTPOINT + 217;
+pub const CURLOPT_MAIL_AUTH: CURLoption = CURLOPTTYPE_OBJECTPOINT + 217;
 // pub const CURLOPT_SASL_IR: CURLoption = CURLOPTTYPE_LONG + 218;
-// pub const CURLOPT_XFERINFOFUNCTION: CURLoption = CURLOPTTYPE_FUNCTIONPOINT + 219;
+pub const CURLOPT_XFERINFOFUNCTION: CURLoption = CURLOPTTYPE_FUNCTIONPOINT + 219;
 // pub const CURLOPT_XOAUTH2_BEARER: CURLoption = CURLOPTTYPE_OBJECTPOINT + 220;
-// pub const CURLOPT_DNS_INTERFACE: CURLoption = CURLOPTTYPE_OBJECTPOINT + 221;
-// pub const CURLOPT_DNS_LOCAL_IP4: CURLoption = CURLOPTTYPE_OBJECTPOINT + 222;
-// pub const CURLOPT_DNS_LOCAL_IP6: CURLoption = CURLOPTTYPE_OBJECTPOINT + 223;
-// pub const CURLOPT_LOGIN_OPTIONS: CURLoption = CURLOPTTYPE_OBJECTPOINT + 224;
+pub const CURLOPT_DNS_INTERFACE: CURLoption = CURLOPTTYPE_OBJECTPOINT + 221;
+pub const CURLOPT_DNS_LOCAL_IP4: CURLoption = CURLOPTTYPE_OBJECTPOINT + 222;
+pub const CURLOPT_DNS_LOCAL_IP6: CURLoption = CURLOPTTYPE_OBJECTPOINT + 223;
+pub const CURLOPT_LOGIN_OPTIONS: CURLoption = CURLOPTTYPE_OBJECTPOINT + 224;
 pub const CURLOPT_EXPECT_100_TIMEOUT_MS: CURLoption = CURLOPTTYPE_LONG + 227;
+pub const CURLOPT_PROXYHEADER: CURLoption = CURLOPTTYPE_OBJECTPOINT + 228;
+pub const CURLOPT_HEADEROPT: CURLoption = CURLOPTTYPE_LONG + 229;
 pub const CURLOPT_PINNEDPUBLICKEY: CURLoption = CURLOPTTYPE_OBJECTPOINT + 230;
 pub const CURLOPT_UNIX_SOCKET_PATH: CURLoption = CURLOPTTYPE_OBJECTPOINT + 231;
 pub const CURLOPT_PATH_AS_IS: CURLoption = CURLOPTTYPE_LONG + 234;
@@ -609,12 +666,19 @@ pub const CURLOPT_PROXY_SSL_OPTIONS: CURLoption = CURLOPTTYPE_LONG + 261;
 
 pub const CURLOPT_ABSTRACT_UNIX_SOCKET: CURLoption = CURLOPTTYPE_OBJECTPOINT + 264;
 
+pub const CURLOPT_MIMEPOST: CURLoption = CURLOPTTYPE_OBJECTPOINT + 269;
+
 pub const CURLOPT_DOH_URL: CURLoption = CURLOPTTYPE_OBJECTPOINT + 279;
 pub const CURLOPT_UPLOAD_BUFFERSIZE: CURLoption = CURLOPTTYPE_LONG + 280;
 
+pub const CURLOPT_CURLU: CURLoption = CURLOPTTYPE_OBJECTPOINT + 282;
+
 pub const CURLOPT_HTTP09_ALLOWED: CURLoption = CURLOPTTYPE_LONG + 285;
 
+pub const CURLOPT_ALTSVC_CTRL: CURLoption = CURLOPTTYPE_LONG + 286;
+pub const CURLOPT_ALTSVC: CURLoption = CURLOPTTYPE_OBJECTPOINT + 287;
 pub const CURLOPT_MAXAGE_CONN: CURLoption = CURLOPTTYPE_LONG + 288;
+pub const CURLOPT_MAIL_RCPT_ALLOWFAILS: CURLoption = CURLOPTTYPE_LONG + 290;
 
 pub const CURLOPT_SSLCERT_BLOB: CURLoption = CURLOPTTYPE_BLOB + 291;
 pub const CURLOPT_SSLKEY_BLOB: CURLoption = CURLOPTTYPE_BLOB + 292;
@@ -625,6 +689,12 @@ pub const CURLOPT_ISSUERCERT_BLOB: CURLoption = CURLOPTTYPE_BLOB + 295;
 pub const CURLOPT_PROXY_ISSUERCERT: CURLoption = CURLOPTTYPE_OBJECTPOINT + 296;
 pub const CURLOPT_PROXY_ISSUERCERT_BLOB: CURLoption = CURLOPTTYPE_BLOB + 297;
 
+pub const CURLOPT_HSTS_CTRL: CURLoption = CURLOPTTYPE_LONG + 299;
+pub const CURLOPT_HSTS: CURLoption = CURLOPTTYPE_OBJECTPOINT + 300;
+pub const CURLOPT_HSTSREADFUNCTION: CURLoption = CURLOPTTYPE_FUNCTIONPOINT + 301;
+pub const CURLOPT_HSTSREADDATA: CURLoption = CURLOPTTYPE_OBJECTPOINT + 302;
+pub const CURLOPT_HSTSWRITEFUNCTION: CURLoption = CURLOPTTYPE_FUNCTIONPOINT + 303;
+pub const CURLOPT_HSTSWRITEDATA: CURLoption = CURLOPTTYPE_OBJECTPOINT + 304;
 pub const CURLOPT_AWS_SIGV4: CURLoption = CURLOPTTYPE_OBJECTPOINT + 305;
 
 pub const CURLOPT_DOH_SSL_VERIFYPEER: CURLoption = CURLOPTTYPE_LONG + 306;
@@ -632,11 +702,17 @@ pub const CURLOPT_DOH_SSL_VERIFYHOST: CURLoption = CURLOPTTYPE_LONG + 307;
 pub const CURLOPT_DOH_SSL_VERIFYSTATUS: CURLoption = CURLOPTTYPE_LONG + 308;
 pub const CURLOPT_CAINFO_BLOB: CURLoption = CURLOPTTYPE_BLOB + 309;
 pub const CURLOPT_PROXY_CAINFO_BLOB: CURLoption = CURLOPTTYPE_BLOB + 310;
+pub const CURLOPT_SSH_HOST_PUBLIC_KEY_SHA256: CURLoption = CURLOPTTYPE_OBJECTPOINT + 311;
+
+pub const CURLOPT_WS_OPTIONS: CURLoption = CURLOPTTYPE_LONG + 320;
 
 pub const CURL_IPRESOLVE_WHATEVER: c_int = 0;
 pub const CURL_IPRESOLVE_V4: c_int = 1;
 pub const CURL_IPRESOLVE_V6: c_int = 2;
 
+pub const CURLHEADER_UNIFIED: c_long = 0;
+pub const CURLHEADER_SEPARATE: c_long = 1 << 0;
+
 pub const CURLSSLOPT_ALLOW_BEAST: c_long = 1 << 0;
 pub const CURLSSLOPT_NO_REVOKE: c_long = 1 << 1;
 pub const CURLSSLOPT_NO_PARTIALCHAIN: c_long = 1 << 2;
@@ -665,6 +741,9 @@ pub const CURL_HTTP_VERSION_2_PRIOR_KNOWLEDGE: c_int = 5;
 /// Use HTTP/3, fallback to HTTP/2 or HTTP/1 if needed.
 /// (Added in CURL 7.66.0)
 pub const CURL_HTTP_VERSION_3: c_int = 30;
+/// Use HTTP/3 without fallback to HTTP/2 or HTTP/1.
+/// (Added in CURL 7.88.0)
+pub const CURL_HTTP_VERSION_3ONLY: c_int = 31;
 
 // Note that the type here is wrong, it's just intended to just be an enum.
 pub const CURL_SSLVERSION_DEFAULT: __enum_ty = 0;
@@ -731,6 +810,11 @@ pub struct curl_forms {
 
 pub type curl_formget_callback = extern "C" fn(*mut c_void, *const c_char, size_t) -> size_t;
 
+pub enum curl_mime {}
+pub enum curl_mimepart {}
+
+pub const CURL_ZERO_TERMINATED: size_t = !0;
+
 #[repr(C)]
 pub struct curl_slist {
     pub data: *mut c_char,
@@ -765,6 +849,7 @@ pub const CURLINFO_STRING: CURLINFO = 0x100000;
 pub const CURLINFO_LONG: CURLINFO = 0x200000;
 pub const CURLINFO_DOUBLE: CURLINFO = 0x300000;
 pub const CURLINFO_SLIST: CURLINFO = 0x400000;
+pub const CURLINFO_OFF_T: CURLINFO = 0x600000;
 pub const CURLINFO_MASK: CURLINFO = 0x0fffff;
 pub const CURLINFO_TYPEMASK: CURLINFO = 0xf00000;
 
@@ -811,6 +896,21 @@ pub const CURLINFO_PRIMARY_PORT: CURLINFO = CURLINFO_LONG + 40;
 pub const CURLINFO_LOCAL_IP: CURLINFO = CURLINFO_STRING + 41;
 pub const CURLINFO_LOCAL_PORT: CURLINFO = CURLINFO_LONG + 42;
 // pub const CURLINFO_TLS_SESSION: CURLINFO = CURLINFO_SLIST + 43;
+pub const CURLINFO_SIZE_UPLOAD_T: CURLINFO = CURLINFO_OFF_T + 7;
+pub const CURLINFO_SIZE_DOWNLOAD_T: CURLINFO = CURLINFO_OFF_T + 8;
+pub const CURLINFO_SPEED_DOWNLOAD_T: CURLINFO = CURLINFO_OFF_T + 9;
+pub const CURLINFO_SPEED_UPLOAD_T: CURLINFO = CURLINFO_OFF_T + 10;
+pub const CURLINFO_TOTAL_TIME_T: CURLINFO = CURLINFO_OFF_T + 50;
+pub const CURLINFO_NAMELOOKUP_TIME_T: CURLINFO = CURLINFO_OFF_T + 51;
+pub const CURLINFO_CONNECT_TIME_T: CURLINFO = CURLINFO_OFF_T + 52;
+pub const CURLINFO_PRETRANSFER_TIME_T: CURLINFO = CURLINFO_OFF_T + 53;
+pub const CURLINFO_STARTTRANSFER_TIME_T: CURLINFO = CURLINFO_OFF_T + 54;
+pub const CURLINFO_REDIRECT_TIME_T: CURLINFO = CURLINFO_OFF_T + 55;
+pub const CURLINFO_APPCONNECT_TIME_T: CURLINFO = CURLINFO_OFF_T + 56;
+pub const CURLINFO_XFER_ID: CURLINFO = CURLINFO_OFF_T + 63;
+pub const CURLINFO_CONN_ID: CURLINFO = CURLINFO_OFF_T + 64;
+pub const CURLINFO_QUEUE_TIME_T: CURLINFO = CURLINFO_OFF_T + 65;
+pub const CURLINFO_POSTTRANSFER_TIME_T: CURLINFO = CURLINFO_OFF_T + 67;
 
 pub type curl_closepolicy = __enum_ty;
 pub const CURLCLOSEPOLICY_NONE: curl_closepolicy = 0;
@@ -834,6 +934,7 @@ pub const CURL_LOCK_DATA_COOKIE: curl_lock_data = 2;
 pub const CURL_LOCK_DATA_DNS: curl_lock_data = 3;
 pub const CURL_LOCK_DATA_SSL_SESSION: curl_lock_data = 4;
 pub const CURL_LOCK_DATA_CONNECT: curl_lock_data = 5;
+pub const CURL_LOCK_DATA_PSL: curl_lock_data = 6;
 
 pub type curl_lock_access = __enum_ty;
 pub const CURL_LOCK_ACCESS_NONE: curl_lock_access = 0;
@@ -862,6 +963,112 @@ pub const CURLSHOPT_LOCKFUNC: CURLSHoption = 3;
 pub const CURLSHOPT_UNLOCKFUNC: CURLSHoption = 4;
 pub const CURLSHOPT_USERDATA: CURLSHoption = 5;
 
+pub enum CURLU {}
+
+pub type CURLUcode = __enum_ty;
+pub const CURLUE_OK: CURLUcode = 0;
+pub const CURLUE_BAD_HANDLE: CURLUcode = 1;
+pub const CURLUE_BAD_PARTPOINTER: CURLUcode = 2;
+pub const CURLUE_MALFORMED_INPUT: CURLUcode = 3;
+pub const CURLUE_BAD_PORT_NUMBER: CURLUcode = 4;
+pub const CURLUE_UNSUPPORTED_SCHEME: CURLUcode = 5;
+pub const CURLUE_URLDECODE: CURLUcode = 6;
+pub const CURLUE_OUT_OF_MEMORY: CURLUcode = 7;
+pub const CURLUE_USER_NOT_ALLOWED: CURLUcode = 8;
+pub const CURLUE_UNKNOWN_PART: CURLUcode = 9;
+pub const CURLUE_NO_SCHEME: CURLUcode = 10;
+pub const CURLUE_NO_USER: CURLUcode = 11;
+pub const CURLUE_NO_PASSWORD: CURLUcode = 12;
+pub const CURLUE_NO_OPTIONS: CURLUcode = 13;
+pub const CURLUE_NO_HOST: CURLUcode = 14;
+pub const CURLUE_NO_PORT: CURLUcode = 15;
+pub const CURLUE_NO_QUERY: CURLUcode = 16;
+pub const CURLUE_NO_FRAGMENT: CURLUcode = 17;
+pub const CURLUE_NO_ZONEID: CURLUcode = 18;
+pub const CURLUE_BAD_FILE_URL: CURLUcode = 19;
+pub const CURLUE_BAD_FRAGMENT: CURLUcode = 20;
+pub const CURLUE_BAD_HOSTNAME: CURLUcode = 21;
+pub const CURLUE_BAD_IPV6: CURLUcode = 22;
+pub const CURLUE_BAD_LOGIN: CURLUcode = 23;
+pub const CURLUE_BAD_PASSWORD: CURLUcode = 24;
+pub const CURLUE_BAD_PATH: CURLUcode = 25;
+pub const CURLUE_BAD_QUERY: CURLUcode = 26;
+pub const CURLUE_BAD_SCHEME: CURLUcode = 27;
+pub const CURLUE_BAD_SLASHES: CURLUcode = 28;
+pub const CURLUE_BAD_USER: CURLUcode = 29;
+
+pub type CURLUPart = __enum_ty;
+pub const CURLUPART_URL: CURLUPart = 0;
+pub const CURLUPART_SCHEME: CURLUPart = 1;
+pub const CURLUPART_USER: CURLUPart = 2;
+pub const CURLUPART_PASSWORD: CURLUPart = 3;
+pub const CURLUPART_OPTIONS: CURLUPart = 4;
+pub const CURLUPART_HOST: CURLUPart = 5;
+pub const CURLUPART_PORT: CURLUPart = 6;
+pub const CURLUPART_PATH: CURLUPart = 7;
+pub const CURLUPART_QUERY: CURLUPart = 8;
+pub const CURLUPART_FRAGMENT: CURLUPart = 9;
+pub const CURLUPART_ZONEID: CURLUPart = 10;
+
+pub const CURLU_DEFAULT_PORT: c_uint = 1 << 0;
+pub const CURLU_NO_DEFAULT_PORT: c_uint = 1 << 1;
+pub const CURLU_DEFAULT_SCHEME: c_uint = 1 << 2;
+pub const CURLU_NON_SUPPORT_SCHEME: c_uint = 1 << 3;
+pub const CURLU_PATH_AS_IS: c_uint = 1 << 4;
+pub const CURLU_DISALLOW_USER: c_uint = 1 << 5;
+pub const CURLU_URLDECODE: c_uint = 1 << 6;
+pub const CURLU_URLENCODE: c_uint = 1 << 7;
+pub const CURLU_APPENDQUERY: c_uint = 1 << 8;
+pub const CURLU_GUESS_SCHEME: c_uint = 1 << 9;
+pub const CURLU_NO_AUTHORITY: c_uint = 1 << 10;
+pub const CURLU_ALLOW_SPACE: c_uint = 1 << 11;
+
+#[repr(C)]
+pub struct curl_ws_frame {
+    pub age: c_int,
+    pub flags: c_int,
+    pub offset: curl_off_t,
+    pub bytesleft: curl_off_t,
+    pub len: size_t,
+}
+
+pub const CURLWS_TEXT: c_uint = 1 << 0;
+pub const CURLWS_BINARY: c_uint = 1 << 1;
+pub const CURLWS_CONT: c_uint = 1 << 2;
+pub const CURLWS_CLOSE: c_uint = 1 << 3;
+pub const CURLWS_PING: c_uint = 1 << 4;
+pub const CURLWS_OFFSET: c_uint = 1 << 5;
+pub const CURLWS_PONG: c_uint = 1 << 6;
+
+pub const CURLWS_RAW_MODE: c_long = 1 << 0;
+pub const CURLWS_NOAUTOPONG: c_long = 1 << 1;
+
+#[repr(C)]
+pub struct curl_header {
+    pub name: *mut c_char,
+    pub value: *mut c_char,
+    pub amount: size_t,
+    pub index: size_t,
+    pub origin: c_uint,
+    pub anchor: *mut c_void,
+}
+
+pub const CURLH_HEADER: c_uint = 1 << 0;
+pub const CURLH_TRAILER: c_uint = 1 << 1;
+pub const CURLH_CONNECT: c_uint = 1 << 2;
+pub const CURLH_1XX: c_uint = 1 << 3;
+pub const CURLH_PSEUDO: c_uint = 1 << 4;
+
+pub type CURLHcode = __enum_ty;
+pub const CURLHE_OK: CURLHcode = 0;
+pub const CURLHE_BADINDEX: CURLHcode = 1;
+pub const CURLHE_MISSING: CURLHcode = 2;
+pub const CURLHE_NOHEADERS: CURLHcode = 3;
+pub const CURLHE_NOREQUEST: CURLHcode = 4;
+pub const CURLHE_OUT_OF_MEMORY: CURLHcode = 5;
+pub const CURLHE_BAD_ARGUMENT: CURLHcode = 6;
+pub const CURLHE_NOT_BUILT_IN: CURLHcode = 7;
+
 pub const CURLVERSION_FIRST: CURLversion = 0;
 pub const CURLVERSION_SECOND: CURLversion = 1;
 pub const CURLVERSION_THIRD: CURLversion = 2;
@@ -1006,6 +1213,15 @@ pub const CURLMOPT_PUSHFUNCTION: CURLMoption = CURLOPTTYPE_FUNCTIONPOINT + 14;
 pub const CURLMOPT_PUSHDATA: CURLMoption = CURLOPTTYPE_OBJECTPOINT + 15;
 pub const CURLMOPT_MAX_CONCURRENT_STREAMS: CURLMoption = CURLOPTTYPE_LONG + 16;
 
+pub const CURL_PUSH_OK: c_int = 0;
+pub const CURL_PUSH_DENY: c_int = 1;
+pub const CURL_PUSH_ERROROUT: c_int = 2;
+
+pub enum curl_pushheaders {}
+
+pub type curl_push_callback =
+    extern "C" fn(*mut CURL, *mut CURL, size_t, *mut curl_pushheaders, *mut c_void) -> c_int;
+
 // These enums are for use with the CURLMOPT_PIPELINING option.
 pub const CURLPIPE_NOTHING: c_long = 0;
 pub const CURLPIPE_HTTP1: c_long = 1;
@@ -1015,6 +1231,7 @@ pub const CURL_ERROR_SIZE: usize = 256;
 
 pub type curl_opensocket_callback =
     extern "C" fn(*mut c_void, curlsocktype, *mut curl_sockaddr) -> curl_socket_t;
+pub type curl_closesocket_callback = extern "C" fn(*mut c_void, curl_socket_t) -> c_int;
 pub type curlsocktype = __enum_ty;
 pub const CURLSOCKTYPE_IPCXN: curlsocktype = 0;
 pub const CURLSOCKTYPE_ACCEPT: curlsocktype = 1;
@@ -1045,6 +1262,34 @@ extern "C" {
     ) -> c_int;
     pub fn curl_formfree(form: *mut curl_httppost);
 
+    pub fn curl_mime_init(easy: *mut CURL) -> *mut curl_mime;
+    pub fn curl_mime_free(mime: *mut curl_mime);
+    pub fn curl_mime_addpart(mime: *mut curl_mime) -> *mut curl_mimepart;
+    pub fn curl_mime_name(part: *mut curl_mimepart, name: *const c_char) -> CURLcode;
+    pub fn curl_mime_filename(part: *mut curl_mimepart, filename: *const c_char) -> CURLcode;
+    pub fn curl_mime_type(part: *mut curl_mimepart, mimetype: *const c_char) -> CURLcode;
+    pub fn curl_mime_encoder(part: *mut curl_mimepart, encoding: *const c_char) -> CURLcode;
+    pub fn curl_mime_data(
+        part: *mut curl_mimepart,
+        data: *const c_char,
+        datasize: size_t,
+    ) -> CURLcode;
+    pub fn curl_mime_filedata(part: *mut curl_mimepart, filename: *const c_char) -> CURLcode;
+    pub fn curl_mime_data_cb(
+        part: *mut curl_mimepart,
+        datasize: curl_off_t,
+        readfunc: curl_read_callback,
+        seekfunc: Option<curl_seek_callback>,
+        freefunc: Option<curl_free_callback>,
+        arg: *mut c_void,
+    ) -> CURLcode;
+    pub fn curl_mime_subparts(part: *mut curl_mimepart, subparts: *mut curl_mime) -> CURLcode;
+    pub fn curl_mime_headers(
+        part: *mut curl_mimepart,
+        headers: *mut curl_slist,
+        take_ownership: c_int,
+    ) -> CURLcode;
+
     pub fn curl_version() -> *mut c_char;
 
     pub fn curl_easy_escape(handle: *mut CURL, string: *const c_char, length: c_int)
@@ -1079,6 +1324,66 @@ extern "C" {
 
     pub fn curl_version_info(t: CURLversion) -> *mut curl_version_info_data;
 
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curl_url() -> *mut CURLU;
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curl_url_cleanup(handle: *mut CURLU);
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curl_url_dup(handle: *const CURLU) -> *mut CURLU;
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curl_url_get(
+        handle: *const CURLU,
+        what: CURLUPart,
+        part: *mut *mut c_char,
+        flags: c_uint,
+    ) -> CURLUcode;
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curl_url_set(
+        handle: *mut CURLU,
+        what: CURLUPart,
+        part: *const c_char,
+        flags: c_uint,
+    ) -> CURLUcode;
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curl_url_strerror(code: CURLUcode) -> *const c_char;
+
+    #[cfg(feature = "ws_7_86_0")]
+    pub fn curl_ws_recv(
+        curl: *mut CURL,
+        buffer: *mut c_void,
+        buflen: size_t,
+        recv: *mut size_t,
+        metap: *mut *const curl_ws_frame,
+    ) -> CURLcode;
+    #[cfg(feature = "ws_7_86_0")]
+    pub fn curl_ws_send(
+        curl: *mut CURL,
+        buffer: *const c_void,
+        buflen: size_t,
+        sent: *mut size_t,
+        fragsize: curl_off_t,
+        flags: c_uint,
+    ) -> CURLcode;
+    #[cfg(feature = "ws_7_86_0")]
+    pub fn curl_ws_meta(curl: *mut CURL) -> *const curl_ws_frame;
+
+    #[cfg(feature = "header_7_83_0")]
+    pub fn curl_easy_header(
+        easy: *mut CURL,
+        name: *const c_char,
+        index: size_t,
+        origin: c_uint,
+        request: c_int,
+        hout: *mut *mut curl_header,
+    ) -> CURLHcode;
+    #[cfg(feature = "header_7_83_0")]
+    pub fn curl_easy_nextheader(
+        easy: *mut CURL,
+        origin: c_uint,
+        request: c_int,
+        prev: *mut curl_header,
+    ) -> *mut curl_header;
+
     pub fn curl_easy_strerror(code: CURLcode) -> *const c_char;
     pub fn curl_share_strerror(code: CURLSHcode) -> *const c_char;
     pub fn curl_easy_pause(handle: *mut CURL, bitmask: c_int) -> CURLcode;
@@ -1165,6 +1470,9 @@ extern "C" {
         sockfd: curl_socket_t,
         sockp: *mut c_void,
     ) -> CURLMcode;
+
+    pub fn curl_pushheader_bynum(h: *mut curl_pushheaders, num: size_t) -> *mut c_char;
+    pub fn curl_pushheader_byname(h: *mut curl_pushheaders, name: *const c_char) -> *mut c_char;
 }
 
 pub fn rust_crate_version() -> &'static str {
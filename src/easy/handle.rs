@@ -4,16 +4,25 @@ use std::io::SeekFrom;
 use std::path::Path;
 use std::ptr;
 use std::str;
+use std::sync::Arc;
 use std::time::Duration;
 
 use libc::c_void;
 
-use crate::easy::handler::{self, InfoType, ReadError, SeekResult, WriteError};
-use crate::easy::handler::{Auth, NetRc, PostRedirections, ProxyType, SslOpt};
-use crate::easy::handler::{HttpVersion, IpResolve, SslVersion, TimeCondition};
-use crate::easy::{Easy2, Handler};
-use crate::easy::{Form, List};
+use crate::easy::handler::{self, InfoType, ReadError, SeekResult, SocketCloser, WriteError};
+use crate::easy::handler::{AltSvcCtrl, CertInfo, HstsCtrl, HstsEntry, TransferStats};
+use crate::easy::handler::{Auth, NetRc, PostRedirections, ProxyType, ResolveEntry, SslOpt};
+use crate::easy::handler::{FtpCreateMissingDirs, FtpFileMethod, FtpSsl};
+use crate::easy::handler::{HttpVersion, IpResolve, SshAuth, SslVersion, TimeCondition};
+use crate::easy::{Easy2, EasyReader, Handler};
+use crate::easy::{Form, List, Mime, Share};
+#[cfg(feature = "header_7_83_0")]
+use crate::easy::{Header, HeaderOrigin, Headers};
 use crate::Error;
+#[cfg(feature = "header_7_83_0")]
+use crate::HeaderError;
+#[cfg(feature = "url_7_62_0")]
+use crate::Url;
 
 /// Raw bindings to a libcurl "easy session".
 ///
@@ -111,8 +120,13 @@ struct Callbacks<'a> {
     header: Option<Box<dyn FnMut(&[u8]) -> bool + 'a>>,
     progress: Option<Box<dyn FnMut(f64, f64, f64, f64) -> bool + 'a>>,
     ssl_ctx: Option<Box<dyn FnMut(*mut c_void) -> Result<(), Error> + 'a>>,
+    hsts_read: Option<Box<dyn FnMut() -> Option<HstsEntry> + 'a>>,
+    hsts_write: Option<Box<HstsWriteFn<'a>>>,
 }
 
+// Called with each entry of the HSTS cache, its index and the total count.
+type HstsWriteFn<'a> = dyn FnMut(&HstsEntry, usize, usize) -> bool + 'a;
+
 impl Easy {
     /// Creates a new "easy" handle which is the core of almost all operations
     /// in libcurl.
@@ -542,6 +556,41 @@ impl Easy {
         Ok(())
     }
 
+    /// Callback to load entries into the HSTS cache.
+    ///
+    /// The callback is invoked at the start of each transfer, repeatedly
+    /// until it returns `None`, when HSTS is enabled with `hsts_ctrl` or
+    /// `hsts_file`.
+    ///
+    /// By default no entries are provided, and this corresponds to the
+    /// `CURLOPT_HSTSREADFUNCTION` and `CURLOPT_HSTSREADDATA` options.
+    ///
+    /// Unlike the other callbacks this can't be set on a `Transfer`, as the
+    /// cache outlives any single transfer.
+    pub fn hsts_read_function<F>(&mut self, f: F) -> Result<(), Error>
+    where
+        F: FnMut() -> Option<HstsEntry> + Send + 'static,
+    {
+        self.inner.get_mut().owned.hsts_read = Some(Box::new(f));
+        Ok(())
+    }
+
+    /// Callback to save entries of the HSTS cache.
+    ///
+    /// The callback is invoked for every entry in the cache, along with its
+    /// index and the total number of entries, when the handle is dropped.
+    /// Returning `false` stops any further entries being passed to it.
+    ///
+    /// By default nothing is saved, and this corresponds to the
+    /// `CURLOPT_HSTSWRITEFUNCTION` and `CURLOPT_HSTSWRITEDATA` options.
+    pub fn hsts_write_function<F>(&mut self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&HstsEntry, usize, usize) -> bool + Send + 'static,
+    {
+        self.inner.get_mut().owned.hsts_write = Some(Box::new(f));
+        Ok(())
+    }
+
     // =========================================================================
     // Error options
 
@@ -560,6 +609,12 @@ impl Easy {
         self.inner.url(url)
     }
 
+    /// Same as [`Easy2::curlu`](struct.Easy2.html#method.curlu)
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curlu(&mut self, url: Url) -> Result<(), Error> {
+        self.inner.curlu(url)
+    }
+
     /// Same as [`Easy2::port`](struct.Easy2.html#method.port)
     pub fn port(&mut self, port: u16) -> Result<(), Error> {
         self.inner.port(port)
@@ -738,6 +793,11 @@ impl Easy {
         self.inner.password(pass)
     }
 
+    /// Same as [`Easy2::login_options`](struct.Easy2.html#method.login_options)
+    pub fn login_options(&mut self, options: &str) -> Result<(), Error> {
+        self.inner.login_options(options)
+    }
+
     /// Same as [`Easy2::http_auth`](struct.Easy2.html#method.http_auth)
     pub fn http_auth(&mut self, auth: &Auth) -> Result<(), Error> {
         self.inner.http_auth(auth)
@@ -831,6 +891,11 @@ impl Easy {
         self.inner.httppost(form)
     }
 
+    /// Same as [`Easy2::mime_post`](struct.Easy2.html#method.mime_post)
+    pub fn mime_post(&mut self, mime: Mime) -> Result<(), Error> {
+        self.inner.mime_post(mime)
+    }
+
     /// Same as [`Easy2::referer`](struct.Easy2.html#method.referer)
     pub fn referer(&mut self, referer: &str) -> Result<(), Error> {
         self.inner.referer(referer)
@@ -846,6 +911,16 @@ impl Easy {
         self.inner.http_headers(list)
     }
 
+    /// Same as [`Easy2::proxy_headers`](struct.Easy2.html#method.proxy_headers)
+    pub fn proxy_headers(&mut self, list: List) -> Result<(), Error> {
+        self.inner.proxy_headers(list)
+    }
+
+    /// Same as [`Easy2::header_opt`](struct.Easy2.html#method.header_opt)
+    pub fn header_opt(&mut self, separate: bool) -> Result<(), Error> {
+        self.inner.header_opt(separate)
+    }
+
     /// Same as [`Easy2::cookie`](struct.Easy2.html#method.cookie)
     pub fn cookie(&mut self, cookie: &str) -> Result<(), Error> {
         self.inner.cookie(cookie)
@@ -871,6 +946,26 @@ impl Easy {
         self.inner.cookie_list(cookie)
     }
 
+    /// Same as [`Easy2::hsts_file`](struct.Easy2.html#method.hsts_file)
+    pub fn hsts_file<P: AsRef<Path>>(&mut self, file: P) -> Result<(), Error> {
+        self.inner.hsts_file(file)
+    }
+
+    /// Same as [`Easy2::hsts_ctrl`](struct.Easy2.html#method.hsts_ctrl)
+    pub fn hsts_ctrl(&mut self, ctrl: &HstsCtrl) -> Result<(), Error> {
+        self.inner.hsts_ctrl(ctrl)
+    }
+
+    /// Same as [`Easy2::alt_svc`](struct.Easy2.html#method.alt_svc)
+    pub fn alt_svc<P: AsRef<Path>>(&mut self, file: P) -> Result<(), Error> {
+        self.inner.alt_svc(file)
+    }
+
+    /// Same as [`Easy2::alt_svc_ctrl`](struct.Easy2.html#method.alt_svc_ctrl)
+    pub fn alt_svc_ctrl(&mut self, ctrl: &AltSvcCtrl) -> Result<(), Error> {
+        self.inner.alt_svc_ctrl(ctrl)
+    }
+
     /// Same as [`Easy2::get`](struct.Easy2.html#method.get)
     pub fn get(&mut self, enable: bool) -> Result<(), Error> {
         self.inner.get(enable)
@@ -891,6 +986,120 @@ impl Easy {
         self.inner.http_transfer_decoding(enable)
     }
 
+    // =========================================================================
+    // SMTP Options
+
+    /// Same as [`Easy2::mail_from`](struct.Easy2.html#method.mail_from)
+    pub fn mail_from(&mut self, from: &str) -> Result<(), Error> {
+        self.inner.mail_from(from)
+    }
+
+    /// Same as [`Easy2::mail_rcpt`](struct.Easy2.html#method.mail_rcpt)
+    pub fn mail_rcpt(&mut self, list: List) -> Result<(), Error> {
+        self.inner.mail_rcpt(list)
+    }
+
+    /// Same as [`Easy2::mail_auth`](struct.Easy2.html#method.mail_auth)
+    pub fn mail_auth(&mut self, auth: &str) -> Result<(), Error> {
+        self.inner.mail_auth(auth)
+    }
+
+    /// Same as [`Easy2::mail_rcpt_allowfails`](struct.Easy2.html#method.mail_rcpt_allowfails)
+    pub fn mail_rcpt_allowfails(&mut self, allow: bool) -> Result<(), Error> {
+        self.inner.mail_rcpt_allowfails(allow)
+    }
+
+    // =========================================================================
+    // FTP Options
+
+    /// Same as [`Easy2::ftp_port`](struct.Easy2.html#method.ftp_port)
+    pub fn ftp_port(&mut self, port: &str) -> Result<(), Error> {
+        self.inner.ftp_port(port)
+    }
+
+    /// Same as [`Easy2::quote`](struct.Easy2.html#method.quote)
+    pub fn quote(&mut self, list: List) -> Result<(), Error> {
+        self.inner.quote(list)
+    }
+
+    /// Same as [`Easy2::postquote`](struct.Easy2.html#method.postquote)
+    pub fn postquote(&mut self, list: List) -> Result<(), Error> {
+        self.inner.postquote(list)
+    }
+
+    /// Same as [`Easy2::prequote`](struct.Easy2.html#method.prequote)
+    pub fn prequote(&mut self, list: List) -> Result<(), Error> {
+        self.inner.prequote(list)
+    }
+
+    /// Same as [`Easy2::ftp_create_missing_dirs`](struct.Easy2.html#method.ftp_create_missing_dirs)
+    pub fn ftp_create_missing_dirs(&mut self, create: FtpCreateMissingDirs) -> Result<(), Error> {
+        self.inner.ftp_create_missing_dirs(create)
+    }
+
+    /// Same as [`Easy2::ftp_use_epsv`](struct.Easy2.html#method.ftp_use_epsv)
+    pub fn ftp_use_epsv(&mut self, enable: bool) -> Result<(), Error> {
+        self.inner.ftp_use_epsv(enable)
+    }
+
+    /// Same as [`Easy2::ftp_use_eprt`](struct.Easy2.html#method.ftp_use_eprt)
+    pub fn ftp_use_eprt(&mut self, enable: bool) -> Result<(), Error> {
+        self.inner.ftp_use_eprt(enable)
+    }
+
+    /// Same as [`Easy2::ftp_filemethod`](struct.Easy2.html#method.ftp_filemethod)
+    pub fn ftp_filemethod(&mut self, method: FtpFileMethod) -> Result<(), Error> {
+        self.inner.ftp_filemethod(method)
+    }
+
+    /// Same as [`Easy2::ftp_ssl`](struct.Easy2.html#method.ftp_ssl)
+    pub fn ftp_ssl(&mut self, level: FtpSsl) -> Result<(), Error> {
+        self.inner.ftp_ssl(level)
+    }
+
+    /// Same as [`Easy2::ftp_skip_pasv_ip`](struct.Easy2.html#method.ftp_skip_pasv_ip)
+    pub fn ftp_skip_pasv_ip(&mut self, skip: bool) -> Result<(), Error> {
+        self.inner.ftp_skip_pasv_ip(skip)
+    }
+
+    /// Same as [`Easy2::dirlistonly`](struct.Easy2.html#method.dirlistonly)
+    pub fn dirlistonly(&mut self, enable: bool) -> Result<(), Error> {
+        self.inner.dirlistonly(enable)
+    }
+
+    /// Same as [`Easy2::append`](struct.Easy2.html#method.append)
+    pub fn append(&mut self, append: bool) -> Result<(), Error> {
+        self.inner.append(append)
+    }
+
+    // =========================================================================
+    // SSH Options
+
+    /// Same as [`Easy2::ssh_auth_types`](struct.Easy2.html#method.ssh_auth_types)
+    pub fn ssh_auth_types(&mut self, auth: &SshAuth) -> Result<(), Error> {
+        self.inner.ssh_auth_types(auth)
+    }
+
+    /// Same as [`Easy2::ssh_public_keyfile`](struct.Easy2.html#method.ssh_public_keyfile)
+    pub fn ssh_public_keyfile<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.inner.ssh_public_keyfile(path)
+    }
+
+    /// Same as [`Easy2::ssh_private_keyfile`](struct.Easy2.html#method.ssh_private_keyfile)
+    pub fn ssh_private_keyfile<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.inner.ssh_private_keyfile(path)
+    }
+
+    /// Same as [`Easy2::ssh_knownhosts`](struct.Easy2.html#method.ssh_knownhosts)
+    pub fn ssh_knownhosts<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.inner.ssh_knownhosts(path)
+    }
+
+    /// Same as [`Easy2::ssh_host_public_key_sha256`](struct.Easy2.html#method.ssh_host_public_key_sha256)
+    pub fn ssh_host_public_key_sha256(&mut self, fingerprint: &str) -> Result<(), Error> {
+        self.inner.ssh_host_public_key_sha256(fingerprint)
+    }
+
     // =========================================================================
     // Protocol Options
 
@@ -1007,11 +1216,36 @@ impl Easy {
         self.inner.resolve(list)
     }
 
+    /// Same as [`Easy2::resolve_entries`](struct.Easy2.html#method.resolve_entries)
+    pub fn resolve_entries(&mut self, entries: &[ResolveEntry]) -> Result<(), Error> {
+        self.inner.resolve_entries(entries)
+    }
+
     /// Same as [`Easy2::connect_only`](struct.Easy2.html#method.connect_only)
     pub fn connect_only(&mut self, enable: bool) -> Result<(), Error> {
         self.inner.connect_only(enable)
     }
 
+    /// Same as [`Easy2::share`](struct.Easy2.html#method.share)
+    pub fn share(&mut self, share: &Share) -> Result<(), Error> {
+        self.inner.share(share)
+    }
+
+    /// Same as [`Easy2::dns_interface`](struct.Easy2.html#method.dns_interface)
+    pub fn dns_interface(&mut self, interface: &str) -> Result<(), Error> {
+        self.inner.dns_interface(interface)
+    }
+
+    /// Same as [`Easy2::dns_local_ip4`](struct.Easy2.html#method.dns_local_ip4)
+    pub fn dns_local_ip4(&mut self, ip: &str) -> Result<(), Error> {
+        self.inner.dns_local_ip4(ip)
+    }
+
+    /// Same as [`Easy2::dns_local_ip6`](struct.Easy2.html#method.dns_local_ip6)
+    pub fn dns_local_ip6(&mut self, ip: &str) -> Result<(), Error> {
+        self.inner.dns_local_ip6(ip)
+    }
+
     // =========================================================================
     // SSL/Security Options
 
@@ -1357,11 +1591,44 @@ impl Easy {
         self.inner.num_connects()
     }
 
+    /// Same as [`Easy2::ftp_entry_path`](struct.Easy2.html#method.ftp_entry_path)
+    pub fn ftp_entry_path(&self) -> Result<Option<&str>, Error> {
+        self.inner.ftp_entry_path()
+    }
+
+    /// Same as [`Easy2::stats`](struct.Easy2.html#method.stats)
+    pub fn stats(&self) -> Result<TransferStats, Error> {
+        self.inner.stats()
+    }
+
+    /// Same as [`Easy2::certinfo_chain`](struct.Easy2.html#method.certinfo_chain)
+    pub fn certinfo_chain(&self) -> Result<Vec<CertInfo>, Error> {
+        self.inner.certinfo_chain()
+    }
+
     /// Same as [`Easy2::cookies`](struct.Easy2.html#method.cookies)
     pub fn cookies(&mut self) -> Result<List, Error> {
         self.inner.cookies()
     }
 
+    /// Same as [`Easy2::header`](struct.Easy2.html#method.header)
+    #[cfg(feature = "header_7_83_0")]
+    pub fn header(
+        &self,
+        name: &str,
+        index: usize,
+        origin: &HeaderOrigin,
+        request: Option<usize>,
+    ) -> Result<Option<Header>, HeaderError> {
+        self.inner.header(name, index, origin, request)
+    }
+
+    /// Same as [`Easy2::headers`](struct.Easy2.html#method.headers)
+    #[cfg(feature = "header_7_83_0")]
+    pub fn headers(&mut self, origin: &HeaderOrigin, request: Option<usize>) -> Headers<'_> {
+        self.inner.headers(origin, request)
+    }
+
     /// Same as [`Easy2::pipewait`](struct.Easy2.html#method.pipewait)
     pub fn pipewait(&mut self, wait: bool) -> Result<(), Error> {
         self.inner.pipewait(wait)
@@ -1424,6 +1691,30 @@ impl Easy {
         }
     }
 
+    /// Same as [`Easy2::into_reader`](struct.Easy2.html#method.into_reader),
+    /// except that the returned reader borrows this handle.
+    ///
+    /// The response body is read through the reader instead of being passed
+    /// to the `write_function` callback, and the handle can be used for
+    /// further transfers once the reader is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::BufRead;
+    ///
+    /// use curl::easy::Easy;
+    ///
+    /// let mut easy = Easy::new();
+    /// easy.url("https://www.rust-lang.org/").unwrap();
+    /// for line in easy.reader().lines() {
+    ///     println!("{}", line.unwrap());
+    /// }
+    /// ```
+    pub fn reader(&mut self) -> EasyReader<'_> {
+        EasyReader::new(self)
+    }
+
     /// Same as [`Easy2::upkeep`](struct.Easy2.html#method.upkeep)
     #[cfg(feature = "upkeep_7_62_0")]
     pub fn upkeep(&self) -> Result<(), Error> {
@@ -1455,6 +1746,19 @@ impl Easy {
         self.inner.reset()
     }
 
+    /// Same as [`Easy2::try_clone_with`](struct.Easy2.html#method.try_clone_with)
+    ///
+    /// Callbacks configured with the `*_function` methods can't be cloned,
+    /// so the new handle starts out without any.
+    pub fn try_clone(&self) -> Result<Easy, Error> {
+        let inner = self.inner.try_clone_with(EasyData {
+            running: Cell::new(false),
+            owned: Callbacks::default(),
+            borrowed: Cell::new(ptr::null_mut()),
+        })?;
+        Ok(Easy { inner })
+    }
+
     /// Same as [`Easy2::recv`](struct.Easy2.html#method.recv)
     pub fn recv(&mut self, data: &mut [u8]) -> Result<usize, Error> {
         self.inner.recv(data)
@@ -1465,6 +1769,18 @@ impl Easy {
         self.inner.send(data)
     }
 
+    pub(crate) fn sockets(&self) -> Arc<SocketCloser> {
+        self.inner.sockets()
+    }
+
+    pub(crate) fn detach_sockets(&mut self) {
+        self.inner.detach_sockets()
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut Easy2<EasyData> {
+        &mut self.inner
+    }
+
     /// Same as [`Easy2::raw`](struct.Easy2.html#method.raw)
     pub fn raw(&self) -> *mut curl_sys::CURL {
         self.inner.raw()
@@ -1573,6 +1889,24 @@ impl Handler for EasyData {
             }
         }
     }
+
+    fn hsts_read(&mut self) -> Option<HstsEntry> {
+        unsafe {
+            match self.callback(|s| &mut s.hsts_read) {
+                Some(hsts_read) => hsts_read(),
+                None => None,
+            }
+        }
+    }
+
+    fn hsts_write(&mut self, entry: &HstsEntry, index: usize, total: usize) -> bool {
+        unsafe {
+            match self.callback(|s| &mut s.hsts_write) {
+                Some(hsts_write) => hsts_write(entry, index, total),
+                None => false,
+            }
+        }
+    }
 }
 
 impl fmt::Debug for EasyData {
@@ -1,23 +1,41 @@
 use std::cell::RefCell;
+use std::cmp;
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::fmt;
 use std::io::{self, SeekFrom, Write};
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::Duration;
 
-use libc::{c_char, c_double, c_int, c_long, c_ulong, c_void, size_t};
-use socket2::Socket;
+use libc::{c_char, c_double, c_int, c_long, c_uint, c_ulong, c_void, size_t};
+use socket2::{SockAddr, SockRef, Socket};
 
 use crate::easy::form;
+#[cfg(feature = "header_7_83_0")]
+use crate::easy::header;
 use crate::easy::list;
+use crate::easy::mime;
+use crate::easy::reader::{ReadBuffer, Reader};
+use crate::easy::share;
 use crate::easy::windows;
-use crate::easy::{Form, List};
+#[cfg(feature = "ws_7_86_0")]
+use crate::easy::WsFrame;
+use crate::easy::{Form, List, Mime, Share};
+#[cfg(feature = "header_7_83_0")]
+use crate::easy::{Header, HeaderOrigin, Headers};
 use crate::panic;
+#[cfg(feature = "url_7_62_0")]
+use crate::url;
 use crate::Error;
+#[cfg(feature = "header_7_83_0")]
+use crate::HeaderError;
+#[cfg(feature = "url_7_62_0")]
+use crate::Url;
 
 /// A trait for the various callbacks used by libcurl to invoke user code.
 ///
@@ -81,6 +99,22 @@ pub trait Handler {
         Ok(data.len())
     }
 
+    /// Callback invoked with received WebSocket data.
+    ///
+    /// When a transfer on a `ws://` or `wss://` URL is performed without
+    /// `connect_only`, the payload of each incoming frame is delivered to
+    /// this callback along with the metadata of that frame, obtained with
+    /// `curl_ws_meta`. Large frames may be delivered in several pieces, see
+    /// `WsFrame::offset` and `WsFrame::bytes_left`.
+    ///
+    /// The return value has the same meaning as for `write`, which this
+    /// forwards to by default.
+    #[cfg(feature = "ws_7_86_0")]
+    fn ws_write(&mut self, data: &[u8], frame: &WsFrame) -> Result<usize, WriteError> {
+        let _ = frame; // ignore unused
+        self.write(data)
+    }
+
     /// Read callback for data uploads.
     ///
     /// This callback function gets called by libcurl as soon as it needs to
@@ -218,6 +252,9 @@ pub trait Handler {
     /// `progress` must be set to `true` to make this function actually get
     /// called.
     ///
+    /// libcurl calls `xferinfo` instead where it is supported, which by
+    /// default forwards to this function with the amounts converted to `f64`.
+    ///
     /// By default this function calls an internal method and corresponds to
     /// `CURLOPT_PROGRESSFUNCTION` and `CURLOPT_PROGRESSDATA`.
     fn progress(&mut self, dltotal: f64, dlnow: f64, ultotal: f64, ulnow: f64) -> bool {
@@ -225,6 +262,25 @@ pub trait Handler {
         true
     }
 
+    /// Callback to progress meter function, with byte counts as integers.
+    ///
+    /// This is the same as `progress`, except that the amounts of data are
+    /// passed as `u64` rather than `f64`, so they stay exact for transfers of
+    /// any size. libcurl calls this rather than `progress` when both are
+    /// available, so only one of the two needs to be implemented.
+    ///
+    /// Returning `false` from this callback will cause libcurl to abort the
+    /// transfer and return `is_aborted_by_callback`.
+    ///
+    /// `progress` must be set to `true` to make this function actually get
+    /// called.
+    ///
+    /// By default this function forwards to `progress` and corresponds to
+    /// `CURLOPT_XFERINFOFUNCTION` and `CURLOPT_XFERINFODATA`.
+    fn xferinfo(&mut self, dltotal: u64, dlnow: u64, ultotal: u64, ulnow: u64) -> bool {
+        self.progress(dltotal as f64, dlnow as f64, ultotal as f64, ulnow as f64)
+    }
+
     /// Callback to SSL context
     ///
     /// This callback function gets called by libcurl just before the
@@ -259,6 +315,127 @@ pub trait Handler {
         ssl_ctx(cx)
     }
 
+    /// Callback to load entries into the HSTS cache.
+    ///
+    /// When HSTS is enabled with `hsts_ctrl` or `hsts_file`, this is called
+    /// at the start of each transfer, repeatedly until it returns `None`.
+    /// Each entry returned is added to the in-memory cache, alongside any
+    /// loaded from the HSTS file.
+    ///
+    /// An entry with an empty host name or one longer than 256 bytes makes
+    /// the transfer fail, while entries which have already expired are
+    /// ignored.
+    ///
+    /// By default no entries are provided, and this corresponds to
+    /// `CURLOPT_HSTSREADFUNCTION` and `CURLOPT_HSTSREADDATA`.
+    fn hsts_read(&mut self) -> Option<HstsEntry> {
+        None
+    }
+
+    /// Callback to save entries of the HSTS cache.
+    ///
+    /// When HSTS is enabled, this is called once for every entry in the cache
+    /// when the handle is dropped. The `index` is the position of `entry` in
+    /// the cache, and `total` the number of entries in it.
+    ///
+    /// Returning `false` from this callback stops libcurl from passing any
+    /// further entries to it.
+    ///
+    /// By default nothing is saved, and this corresponds to
+    /// `CURLOPT_HSTSWRITEFUNCTION` and `CURLOPT_HSTSWRITEDATA`.
+    fn hsts_write(&mut self, entry: &HstsEntry, index: usize, total: usize) -> bool {
+        let _ = (entry, index, total); // ignore unused
+        false
+    }
+
+    /// Callback called before each file of a wildcard transfer is downloaded.
+    ///
+    /// When `wildcard_match` is enabled, this is called with information
+    /// about every file matching the pattern, before it's transferred.
+    /// `remaining` is the number of matching files left, including this one.
+    /// Returning `ChunkAction::Skip` moves on to the next file without
+    /// downloading this one, while `ChunkAction::Fail` aborts the transfer
+    /// with `is_chunk_failed`.
+    ///
+    /// Note that libcurl only downloads regular files, skipping anything else
+    /// after calling this function.
+    ///
+    /// By default every file is downloaded, and this corresponds to
+    /// `CURLOPT_CHUNK_BGN_FUNCTION` and `CURLOPT_CHUNK_DATA`.
+    fn chunk_begin(&mut self, info: &FileInfo, remaining: u32) -> ChunkAction {
+        let _ = (info, remaining); // ignore unused
+        ChunkAction::Ok
+    }
+
+    /// Callback called after each file of a wildcard transfer.
+    ///
+    /// This is called once for every call to `chunk_begin`, after the file
+    /// has been downloaded or skipped. Returning `false` aborts the transfer
+    /// with `is_chunk_failed`.
+    ///
+    /// By default this function does nothing and corresponds to
+    /// `CURLOPT_CHUNK_END_FUNCTION` and `CURLOPT_CHUNK_DATA`.
+    fn chunk_end(&mut self) -> bool {
+        true
+    }
+
+    /// Callback to match file names against the pattern of a wildcard
+    /// transfer.
+    ///
+    /// When `wildcard_match` and `fnmatch_function` are enabled, this is
+    /// called for every entry of the directory listing with the last part of
+    /// the URL as `pattern`, and should return whether `name` matches it.
+    /// Both are passed as the raw bytes of the URL and the listing, which
+    /// need not be valid UTF-8. Without `fnmatch_function` libcurl matches
+    /// the names itself.
+    ///
+    /// By default this function matches nothing and corresponds to
+    /// `CURLOPT_FNMATCH_FUNCTION` and `CURLOPT_FNMATCH_DATA`.
+    fn fnmatch(&mut self, pattern: &[u8], name: &[u8]) -> bool {
+        let _ = (pattern, name); // ignore unused
+        false
+    }
+
+    /// Callback to supply addresses for the hosts of a transfer.
+    ///
+    /// This is called with the URL of every transfer right before it starts,
+    /// from `perform` or when the handle is added to a `Multi`, so addresses
+    /// can be looked up for each request, such as from a service discovery
+    /// layer. The returned entries are loaded into libcurl's DNS cache for
+    /// the transfer along with those set with `resolve` or
+    /// `resolve_entries`.
+    ///
+    /// Note that libcurl only loads the entries when the transfer starts, so
+    /// hosts it is redirected to afterwards are resolved as usual unless
+    /// entries for them are returned up front. Entries also stay in the DNS
+    /// cache after the transfer unless they're made transient with
+    /// `ResolveEntry::set_transient`.
+    ///
+    /// By default this function returns no entries, and the entries are
+    /// passed to libcurl with `CURLOPT_RESOLVE`.
+    fn resolve(&mut self, url: &str) -> Vec<ResolveEntry> {
+        let _ = url; // ignore unused
+        Vec::new()
+    }
+
+    /// Callback to decide whether to trust the host key of an SSH server.
+    ///
+    /// When `ssh_knownhosts` is set, this is called with the key the server
+    /// presented as `found`, the key recorded for the host in the known hosts
+    /// file as `known` if there is one, and how the two compare as `matched`.
+    /// The returned `KhStat` tells libcurl whether to go ahead with the
+    /// connection and whether to update the known hosts file.
+    ///
+    /// By default the connection is only accepted if the keys match, and this
+    /// corresponds to `CURLOPT_SSH_KEYFUNCTION` and `CURLOPT_SSH_KEYDATA`.
+    fn ssh_key(&mut self, known: Option<&KhKey>, found: &KhKey, matched: KhMatch) -> KhStat {
+        let _ = (known, found); // ignore unused
+        match matched {
+            KhMatch::Ok => KhStat::Fine,
+            _ => KhStat::Reject,
+        }
+    }
+
     /// Callback to open sockets for libcurl.
     ///
     /// This callback function gets called by libcurl instead of the socket(2)
@@ -270,6 +447,8 @@ pub trait Handler {
     /// libcurl and it will return `is_couldnt_connect` from the function that
     /// triggered this callback.
     ///
+    /// Sockets returned from this function are closed through `close_socket`.
+    ///
     /// By default this function opens a standard socket and
     /// corresponds to `CURLOPT_OPENSOCKETFUNCTION `.
     fn open_socket(
@@ -297,6 +476,64 @@ pub trait Handler {
             socket.into_raw_socket()
         }
     }
+
+    /// Callback to open sockets for libcurl, knowing what they're for.
+    ///
+    /// This is called by libcurl to open a socket like `open_socket`, with
+    /// the `purpose` of the socket and the `address` libcurl is going to
+    /// connect it to (or bind it to, for listening sockets such as with
+    /// active FTP). The address is `None` for addresses which aren't IP
+    /// addresses, such as Unix domain sockets.
+    ///
+    /// By default this function calls `open_socket`.
+    fn open_socket_for(
+        &mut self,
+        family: c_int,
+        socktype: c_int,
+        protocol: c_int,
+        purpose: SockType,
+        address: Option<SocketAddr>,
+    ) -> Option<curl_sys::curl_socket_t> {
+        let _ = (purpose, address); // ignore unused
+        self.open_socket(family, socktype, protocol)
+    }
+
+    /// Callback to set options on sockets created by libcurl.
+    ///
+    /// This is called after a socket is created but before it's connected,
+    /// with `purpose` telling what the socket is for. It can be used to set
+    /// options such as `SO_MARK`, `IP_TOS` or buffer sizes through `socket`.
+    /// Returning `SockOptResult::Error` aborts the connection, and
+    /// `SockOptResult::AlreadyConnected` tells libcurl that the socket (such
+    /// as one returned from `open_socket`) is already connected.
+    ///
+    /// By default this function does nothing and corresponds to
+    /// `CURLOPT_SOCKOPTFUNCTION` and `CURLOPT_SOCKOPTDATA`.
+    fn sockopt(&mut self, socket: &SockRef, purpose: SockType) -> SockOptResult {
+        let _ = (socket, purpose); // ignore unused
+        SockOptResult::Ok
+    }
+
+    /// Callback to close sockets for libcurl.
+    ///
+    /// This callback function gets called by libcurl instead of the close(2)
+    /// call for sockets returned from `open_socket` or `open_socket_for`, and
+    /// is responsible for closing `socket`. It is only ever called through
+    /// this handle, while the handle is performing a transfer or being
+    /// driven by the `Multi` it's attached to.
+    ///
+    /// Note that libcurl may keep connections open after a transfer to reuse
+    /// them, and a `Multi` may hold on to them after this handle has been
+    /// removed from it or dropped. Sockets closed at that point are closed
+    /// without calling this function, as are the sockets of handles which
+    /// share connections through a `Share` with `connect` enabled, which may
+    /// be closed by any of them.
+    ///
+    /// By default this function closes the socket and corresponds to
+    /// `CURLOPT_CLOSESOCKETFUNCTION`.
+    fn close_socket(&mut self, socket: curl_sys::curl_socket_t) {
+        close_raw_socket(socket)
+    }
 }
 
 pub fn debug(kind: InfoType, data: &[u8]) {
@@ -382,15 +619,122 @@ pub struct Easy2<H> {
 struct Inner<H> {
     handle: *mut curl_sys::CURL,
     header_list: Option<List>,
+    proxy_header_list: Option<List>,
+    mail_rcpt_list: Option<List>,
+    quote_list: Option<List>,
+    postquote_list: Option<List>,
+    prequote_list: Option<List>,
     resolve_list: Option<List>,
+    // The list of `resolve_list` and the entries from `Handler::resolve`
+    // for the last transfer.
+    resolved_list: Option<List>,
     connect_to_list: Option<List>,
-    form: Option<Form>,
+    form: Option<Arc<SharedForm>>,
+    mime: Option<Mime>,
+    share: Option<Share>,
+    #[cfg(feature = "url_7_62_0")]
+    curlu: Option<Url>,
+    sockets: Arc<SocketCloser>,
+    fnmatch: bool,
+    url: Option<String>,
+    resolve: fn(&mut H, &str) -> Vec<ResolveEntry>,
+    // The pointer the callbacks get, which `perform` uses too as it only
+    // borrows the handle.
+    this: *mut Inner<H>,
+    reader: Option<ReadBuffer>,
     error_buf: RefCell<Vec<u8>>,
     handler: H,
 }
 
 unsafe impl<H: Send> Send for Inner<H> {}
 
+impl<H> Inner<H> {
+    // Whether connections of this handle may be reused and closed by other
+    // handles sharing them.
+    fn shares_connections(&self) -> bool {
+        self.share.as_ref().is_some_and(share::shares_connections)
+    }
+}
+
+type CloseFn = unsafe fn(*mut c_void, curl_sys::curl_socket_t);
+
+// Closes sockets on behalf of a handle, which libcurl hands to
+// `closesocket_cb` for the sockets of the connections the handle created.
+// libcurl may close those long after the handle left the connection cache
+// of a `Multi`, so the multi handle keeps the closer alive for as long as
+// sockets opened through it are left.
+#[derive(Debug)]
+pub(crate) struct SocketCloser {
+    state: Mutex<CloserState>,
+}
+
+#[derive(Debug)]
+struct CloserState {
+    handler: Option<(*mut c_void, CloseFn)>,
+    sockets: Vec<curl_sys::curl_socket_t>,
+}
+
+// The handler is only called with the lock held, and the handle detaches from
+// the closer before it goes away or moves on to another connection cache.
+unsafe impl Send for SocketCloser {}
+unsafe impl Sync for SocketCloser {}
+
+impl SocketCloser {
+    fn new(handler: Option<(*mut c_void, CloseFn)>) -> Arc<SocketCloser> {
+        Arc::new(SocketCloser {
+            state: Mutex::new(CloserState {
+                handler,
+                sockets: Vec::new(),
+            }),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CloserState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn attach(&self, handler: Option<(*mut c_void, CloseFn)>) {
+        self.lock().handler = handler;
+    }
+
+    fn detach(&self) -> Option<(*mut c_void, CloseFn)> {
+        self.lock().handler.take()
+    }
+
+    fn opened(&self, socket: curl_sys::curl_socket_t) {
+        self.lock().sockets.push(socket);
+    }
+
+    // Whether no socket opened through this closer is left open.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.lock().sockets.is_empty()
+    }
+
+    // Only sockets opened through the handler are handed back to it, others
+    // such as those of a connection reused by another handle are closed
+    // directly.
+    fn close(&self, socket: curl_sys::curl_socket_t) {
+        let mut state = self.lock();
+        match state.sockets.iter().position(|s| *s == socket) {
+            Some(i) => {
+                state.sockets.swap_remove(i);
+                match state.handler {
+                    Some((data, close)) => unsafe { close(data, socket) },
+                    None => close_raw_socket(socket),
+                }
+            }
+            None => close_raw_socket(socket),
+        }
+    }
+}
+
+// libcurl only reads a form once it is set, so it is shared between a handle
+// and its clones.
+struct SharedForm(Form);
+
+unsafe impl Send for SharedForm {}
+unsafe impl Sync for SharedForm {}
+
 /// Possible proxy types that libcurl currently understands.
 #[non_exhaustive]
 #[allow(missing_docs)]
@@ -460,6 +804,13 @@ pub enum HttpVersion {
     ///
     /// (Added in CURL 7.66.0)
     V3 = curl_sys::CURL_HTTP_VERSION_3 as isize,
+
+    /// Setting this value will make libcurl attempt to use HTTP/3 directly to
+    /// server given in the URL, failing the transfer if the HTTP/3 connection
+    /// can't be established.
+    ///
+    /// (Added in CURL 7.88.0)
+    V3Only = curl_sys::CURL_HTTP_VERSION_3ONLY as isize,
 }
 
 /// Possible values to pass to the `ssl_version` and `ssl_min_max_version` method.
@@ -539,6 +890,61 @@ pub enum WriteError {
     Pause,
 }
 
+/// Possible return values from the `chunk_begin` callback.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum ChunkAction {
+    /// Indicates that the file should be downloaded.
+    Ok = curl_sys::CURL_CHUNK_BGN_FUNC_OK as isize,
+
+    /// Indicates that the file should be skipped, moving on to the next one.
+    Skip = curl_sys::CURL_CHUNK_BGN_FUNC_SKIP as isize,
+
+    /// Indicates that the whole transfer should be aborted.
+    Fail = curl_sys::CURL_CHUNK_BGN_FUNC_FAIL as isize,
+}
+
+/// What a socket passed to `Handler::sockopt` is for.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SockType {
+    /// The socket is for a connection to a server.
+    Connection,
+
+    /// The socket accepts a connection from a server, such as for active FTP.
+    Accept,
+}
+
+/// Possible return values from the `sockopt` callback.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum SockOptResult {
+    /// Indicates that the socket should be used.
+    Ok = curl_sys::CURL_SOCKOPT_OK as isize,
+
+    /// Indicates that the connection should be aborted.
+    Error = curl_sys::CURL_SOCKOPT_ERROR as isize,
+
+    /// Indicates that the socket is already connected, and libcurl should
+    /// skip connecting it.
+    AlreadyConnected = curl_sys::CURL_SOCKOPT_ALREADY_CONNECTED as isize,
+}
+
+/// Possible types of the files described by `FileInfo`.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    File,
+    Directory,
+    Symlink,
+    DeviceBlock,
+    DeviceChar,
+    NamedPipe,
+    Socket,
+    Door,
+}
+
 /// Options for `.netrc` parsing.
 #[derive(Debug, Clone, Copy)]
 pub enum NetRc {
@@ -558,6 +964,111 @@ pub enum NetRc {
     Required = curl_sys::CURL_NETRC_REQUIRED as isize,
 }
 
+/// Possible values to pass to the `ftp_ssl` method.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum FtpSsl {
+    /// Don't attempt to use SSL/TLS.
+    ///
+    /// This option is default
+    None = curl_sys::CURLUSESSL_NONE as isize,
+
+    /// Try using SSL/TLS, but proceed anyway if the server doesn't support it.
+    Try = curl_sys::CURLUSESSL_TRY as isize,
+
+    /// Require SSL/TLS for the control connection, or fail.
+    Control = curl_sys::CURLUSESSL_CONTROL as isize,
+
+    /// Require SSL/TLS for all communication, or fail.
+    All = curl_sys::CURLUSESSL_ALL as isize,
+}
+
+/// Possible values to pass to the `ftp_create_missing_dirs` method.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum FtpCreateMissingDirs {
+    /// Don't create any directories, failing if one of them doesn't exist.
+    ///
+    /// This option is default
+    None = curl_sys::CURLFTP_CREATE_DIR_NONE as isize,
+
+    /// Create the directories of the path which don't exist yet.
+    Create = curl_sys::CURLFTP_CREATE_DIR as isize,
+
+    /// Same as `Create`, but if creating a directory fails also retry to
+    /// change into it, in case another client created it in the meantime.
+    CreateRetry = curl_sys::CURLFTP_CREATE_DIR_RETRY as isize,
+}
+
+/// Possible values to pass to the `ftp_filemethod` method.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum FtpFileMethod {
+    /// Let libcurl pick, which is currently the same as `MultiCwd`.
+    Default = curl_sys::CURLFTPMETHOD_DEFAULT as isize,
+
+    /// Issue one `CWD` command for each directory of the path.
+    MultiCwd = curl_sys::CURLFTPMETHOD_MULTICWD as isize,
+
+    /// Don't issue any `CWD` commands and pass the full path to the commands
+    /// operating on the file instead.
+    NoCwd = curl_sys::CURLFTPMETHOD_NOCWD as isize,
+
+    /// Issue a single `CWD` command with the full directory of the path.
+    SingleCwd = curl_sys::CURLFTPMETHOD_SINGLECWD as isize,
+}
+
+/// Possible types of the keys described by `KhKey`.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KhType {
+    Unknown,
+    Rsa1,
+    Rsa,
+    Dss,
+    Ecdsa,
+    Ed25519,
+}
+
+/// How the key of an SSH server compares to the known hosts file, passed to
+/// `Handler::ssh_key`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KhMatch {
+    /// The host was found with a matching key.
+    Ok,
+
+    /// The host was found, but with a different key.
+    Mismatch,
+
+    /// The host wasn't found.
+    Missing,
+}
+
+/// Possible return values from the `ssh_key` callback.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy)]
+pub enum KhStat {
+    /// Accept the key and add it to the known hosts file.
+    FineAddToFile = curl_sys::CURLKHSTAT_FINE_ADD_TO_FILE as isize,
+
+    /// Accept the key.
+    Fine = curl_sys::CURLKHSTAT_FINE as isize,
+
+    /// Reject the key, failing the transfer.
+    Reject = curl_sys::CURLKHSTAT_REJECT as isize,
+
+    /// Neither accept nor reject the key for now, leaving the connection
+    /// waiting, which requires the multi interface.
+    Defer = curl_sys::CURLKHSTAT_DEFER as isize,
+
+    /// Accept the key and replace the one in the known hosts file with it.
+    ///
+    /// This requires libcurl 7.73.0 or later.
+    FineReplace = curl_sys::CURLKHSTAT_FINE_REPLACE as isize,
+}
+
 /// Structure which stores possible authentication methods to get passed to
 /// `http_auth` and `proxy_auth`.
 #[derive(Clone)]
@@ -570,11 +1081,108 @@ pub struct Auth {
 pub struct SslOpt {
     bits: c_long,
 }
+
+/// Structure which stores possible SSH authentication methods to pass to
+/// `ssh_auth_types`.
+#[derive(Clone, Default)]
+pub struct SshAuth {
+    bits: c_ulong,
+}
 /// Structure which stores possible post redirection options to pass to `post_redirections`.
 pub struct PostRedirections {
     bits: c_ulong,
 }
 
+/// Structure which stores possible HSTS options to pass to `hsts_ctrl`.
+#[derive(Clone, Default)]
+pub struct HstsCtrl {
+    bits: c_long,
+}
+
+/// Information about a certificate in a TLS certificate chain, returned from
+/// `Easy2::certinfo_chain`.
+///
+/// This holds the fields libcurl extracted from the certificate as key/value
+/// pairs, such as `Subject`, `Issuer`, `Start date`, `Expire date` and `Cert`.
+/// The exact set of fields depends on the TLS backend libcurl is built with.
+#[derive(Debug, Clone)]
+pub struct CertInfo {
+    fields: Vec<(String, String)>,
+}
+
+/// Information about a file of a wildcard transfer, passed to
+/// `Handler::chunk_begin`.
+///
+/// This is what libcurl could parse from the server's directory listing, so
+/// any of the fields may be unknown.
+pub struct FileInfo<'a> {
+    raw: &'a curl_sys::curl_fileinfo,
+}
+
+/// An SSH host key, passed to `Handler::ssh_key`.
+pub struct KhKey<'a> {
+    raw: &'a curl_sys::curl_khkey,
+}
+
+/// Timing and size information about a transfer, returned from
+/// `Easy2::stats`.
+///
+/// All times are measured from the start of the transfer. Values which the
+/// version of libcurl in use doesn't provide are `None`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TransferStats {
+    queue_time: Option<Duration>,
+    namelookup_time: Duration,
+    connect_time: Duration,
+    appconnect_time: Duration,
+    pretransfer_time: Duration,
+    posttransfer_time: Option<Duration>,
+    starttransfer_time: Duration,
+    total_time: Duration,
+    redirect_time: Duration,
+    redirect_count: u32,
+    download_size: u64,
+    upload_size: u64,
+    download_speed: u64,
+    upload_speed: u64,
+    header_size: u64,
+    request_size: u64,
+    num_connects: u64,
+    conn_id: Option<u64>,
+    xfer_id: Option<u64>,
+}
+
+/// An entry for libcurl's DNS cache, passed to `Easy2::resolve_entries`.
+///
+/// This is the typed form of the `host:port:address` strings taken by
+/// `Easy2::resolve`, and formats as one with `to_string`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveEntry {
+    host: String,
+    port: u16,
+    addresses: Vec<IpAddr>,
+    removal: bool,
+    transient: bool,
+}
+
+/// Structure which stores possible Alt-Svc options to pass to `alt_svc_ctrl`.
+#[derive(Clone, Default)]
+pub struct AltSvcCtrl {
+    bits: c_long,
+}
+
+/// An entry of the HSTS cache, passed to `Handler::hsts_read` and
+/// `Handler::hsts_write`.
+///
+/// This corresponds to the `curl_hstsentry` struct in libcurl.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HstsEntry {
+    host: String,
+    include_subdomains: bool,
+    expire: Option<String>,
+}
+
 impl<H: Handler> Easy2<H> {
     /// Creates a new "easy" handle which is the core of almost all operations
     /// in libcurl.
@@ -588,22 +1196,67 @@ impl<H: Handler> Easy2<H> {
         unsafe {
             let handle = curl_sys::curl_easy_init();
             assert!(!handle.is_null());
-            let mut ret = Easy2 {
-                inner: Box::new(Inner {
-                    handle,
-                    header_list: None,
-                    resolve_list: None,
-                    connect_to_list: None,
-                    form: None,
-                    error_buf: RefCell::new(vec![0; curl_sys::CURL_ERROR_SIZE]),
-                    handler,
-                }),
-            };
+            let mut ret = Easy2::from_raw(handle, handler);
             ret.default_configure();
             ret
         }
     }
 
+    // Takes ownership of an easy handle created by libcurl, such as one for
+    // an HTTP/2 server push.
+    //
+    // Depending on the version of libcurl the handle may be a duplicate of
+    // another one, so the lists which would still point into the other
+    // handle's `Inner` are reset here. The callbacks aren't bound to the new
+    // handler until `bind` is called, so libcurl can still discard the handle
+    // if this is dropped before then.
+    pub(crate) unsafe fn from_duplicate(handle: *mut curl_sys::CURL, handler: H) -> Easy2<H> {
+        let mut ret = Easy2::from_raw(handle, handler);
+        let null = ptr::null::<c_char>();
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_HTTPHEADER, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_PROXYHEADER, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_MAIL_RCPT, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_QUOTE, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_POSTQUOTE, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_PREQUOTE, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_RESOLVE, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_CONNECT_TO, null));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_HTTPPOST, null));
+        drop(ret.setopt_long(curl_sys::CURLOPT_HTTPGET, 1));
+        drop(ret.setopt_ptr(curl_sys::CURLOPT_PRIVATE, null));
+        ret
+    }
+
+    unsafe fn from_raw(handle: *mut curl_sys::CURL, handler: H) -> Easy2<H> {
+        Easy2 {
+            inner: Box::new(Inner {
+                handle,
+                header_list: None,
+                proxy_header_list: None,
+                mail_rcpt_list: None,
+                quote_list: None,
+                postquote_list: None,
+                prequote_list: None,
+                resolve_list: None,
+                resolved_list: None,
+                connect_to_list: None,
+                form: None,
+                mime: None,
+                share: None,
+                #[cfg(feature = "url_7_62_0")]
+                curlu: None,
+                sockets: SocketCloser::new(None),
+                fnmatch: false,
+                url: None,
+                resolve: H::resolve,
+                this: ptr::null_mut(),
+                reader: None,
+                error_buf: RefCell::new(vec![0; curl_sys::CURL_ERROR_SIZE]),
+                handler,
+            }),
+        }
+    }
+
     /// Re-initializes this handle to the default values.
     ///
     /// This puts the handle to the same state as it was in when it was just
@@ -613,18 +1266,60 @@ impl<H: Handler> Easy2<H> {
         unsafe {
             curl_sys::curl_easy_reset(self.inner.handle);
         }
+        // libcurl no longer refers to any of these, so don't hand them to
+        // clones of this handle either.
+        self.inner.header_list = None;
+        self.inner.proxy_header_list = None;
+        self.inner.mail_rcpt_list = None;
+        self.inner.quote_list = None;
+        self.inner.postquote_list = None;
+        self.inner.prequote_list = None;
+        self.inner.resolve_list = None;
+        self.inner.resolved_list = None;
+        self.inner.url = None;
+        self.inner.connect_to_list = None;
+        self.inner.form = None;
+        self.inner.mime = None;
+        #[cfg(feature = "url_7_62_0")]
+        {
+            self.inner.curlu = None;
+        }
+        self.inner.fnmatch = false;
         self.default_configure();
     }
 
+    /// Indicates whether `Handler::fnmatch` matches the file names of a
+    /// wildcard transfer, instead of libcurl's own matching.
+    ///
+    /// By default this option is `false` and corresponds to
+    /// `CURLOPT_FNMATCH_FUNCTION`.
+    pub fn fnmatch_function(&mut self, enable: bool) -> Result<(), Error> {
+        let cb = if enable {
+            let cb: curl_sys::curl_fnmatch_callback = fnmatch_cb::<H>;
+            cb as *const c_char
+        } else {
+            ptr::null()
+        };
+        self.setopt_ptr(curl_sys::CURLOPT_FNMATCH_FUNCTION, cb)?;
+        self.inner.fnmatch = enable;
+        Ok(())
+    }
+
     fn default_configure(&mut self) {
+        self.bind();
+        let _ = self.signal(false);
+        self.ssl_configure();
+    }
+
+    // Points the error buffer and all callbacks at this handle's `Inner`.
+    pub(crate) fn bind(&mut self) {
         self.setopt_ptr(
             curl_sys::CURLOPT_ERRORBUFFER,
             self.inner.error_buf.borrow().as_ptr() as *const _,
         )
         .expect("failed to set error buffer");
-        let _ = self.signal(false);
-        self.ssl_configure();
 
+        self.inner.this = &mut *self.inner;
         let ptr = &*self.inner as *const _ as *const _;
 
         let cb: extern "C" fn(*mut c_char, size_t, size_t, *mut c_void) -> size_t = header_cb::<H>;
@@ -657,6 +1352,11 @@ impl<H: Handler> Easy2<H> {
         self.setopt_ptr(curl_sys::CURLOPT_PROGRESSDATA, ptr)
             .expect("failed to set progress callback");
 
+        // Older versions of libcurl only know about the progress callback
+        // above, which is what gets called there instead.
+        let cb: curl_sys::curl_xferinfo_callback = xferinfo_cb::<H>;
+        drop(self.setopt_ptr(curl_sys::CURLOPT_XFERINFOFUNCTION, cb as *const _));
+
         let cb: curl_sys::curl_debug_callback = debug_cb::<H>;
         self.setopt_ptr(curl_sys::CURLOPT_DEBUGFUNCTION, cb as *const _)
             .expect("failed to set debug callback");
@@ -667,11 +1367,50 @@ impl<H: Handler> Easy2<H> {
         drop(self.setopt_ptr(curl_sys::CURLOPT_SSL_CTX_FUNCTION, cb as *const _));
         drop(self.setopt_ptr(curl_sys::CURLOPT_SSL_CTX_DATA, ptr));
 
+        // These fail if libcurl is built without HSTS support.
+        let cb: curl_sys::curl_hstsread_callback = hstsread_cb::<H>;
+        drop(self.setopt_ptr(curl_sys::CURLOPT_HSTSREADFUNCTION, cb as *const _));
+        drop(self.setopt_ptr(curl_sys::CURLOPT_HSTSREADDATA, ptr));
+
+        let cb: curl_sys::curl_hstswrite_callback = hstswrite_cb::<H>;
+        drop(self.setopt_ptr(curl_sys::CURLOPT_HSTSWRITEFUNCTION, cb as *const _));
+        drop(self.setopt_ptr(curl_sys::CURLOPT_HSTSWRITEDATA, ptr));
+
+        // These fail if libcurl is built without FTP support.
+        let cb: curl_sys::curl_chunk_bgn_callback = chunk_bgn_cb::<H>;
+        drop(self.setopt_ptr(curl_sys::CURLOPT_CHUNK_BGN_FUNCTION, cb as *const _));
+        let cb: curl_sys::curl_chunk_end_callback = chunk_end_cb::<H>;
+        drop(self.setopt_ptr(curl_sys::CURLOPT_CHUNK_END_FUNCTION, cb as *const _));
+        drop(self.setopt_ptr(curl_sys::CURLOPT_CHUNK_DATA, ptr));
+
+        drop(self.setopt_ptr(curl_sys::CURLOPT_FNMATCH_DATA, ptr));
+        let fnmatch = self.inner.fnmatch;
+        drop(self.fnmatch_function(fnmatch));
+
+        // These fail if libcurl is built without SSH support.
+        let cb: curl_sys::curl_sshkeycallback = ssh_key_cb::<H>;
+        drop(self.setopt_ptr(curl_sys::CURLOPT_SSH_KEYFUNCTION, cb as *const _));
+        drop(self.setopt_ptr(curl_sys::CURLOPT_SSH_KEYDATA, ptr));
+
         let cb: curl_sys::curl_opensocket_callback = opensocket_cb::<H>;
         self.setopt_ptr(curl_sys::CURLOPT_OPENSOCKETFUNCTION, cb as *const _)
             .expect("failed to set open socket callback");
         self.setopt_ptr(curl_sys::CURLOPT_OPENSOCKETDATA, ptr)
             .expect("failed to set open socket callback");
+
+        let cb: curl_sys::curl_sockopt_callback = sockopt_cb::<H>;
+        self.setopt_ptr(curl_sys::CURLOPT_SOCKOPTFUNCTION, cb as *const _)
+            .expect("failed to set sockopt callback");
+        self.setopt_ptr(curl_sys::CURLOPT_SOCKOPTDATA, ptr)
+            .expect("failed to set sockopt callback");
+
+        let cb: curl_sys::curl_closesocket_callback = closesocket_cb;
+        self.setopt_ptr(curl_sys::CURLOPT_CLOSESOCKETFUNCTION, cb as *const _)
+            .expect("failed to set close socket callback");
+        let data = &mut *self.inner as *mut Inner<H> as *mut c_void;
+        let close: CloseFn = close_socket::<H>;
+        self.inner.sockets.attach(Some((data, close)));
+        self.set_socket_closer();
     }
 
     #[cfg(need_openssl_probe)]
@@ -865,8 +1604,27 @@ impl<H> Easy2<H> {
     /// By default this option is not set and `perform` will not work until it
     /// is set. This option corresponds to `CURLOPT_URL`.
     pub fn url(&mut self, url: &str) -> Result<(), Error> {
-        let url = CString::new(url)?;
-        self.setopt_str(curl_sys::CURLOPT_URL, &url)
+        let cstr = CString::new(url)?;
+        self.setopt_str(curl_sys::CURLOPT_URL, &cstr)?;
+        self.inner.url = Some(url.to_string());
+        Ok(())
+    }
+
+    /// Provides the URL to use in the request as an already parsed `Url`.
+    ///
+    /// The handle keeps the `Url` alive for as long as it is needed, and it
+    /// takes precedence over any URL configured with the `url` method. Since
+    /// libcurl uses the parsed handle directly, the URL of the request is
+    /// exactly what `Url::url` reports.
+    ///
+    /// By default this option is not set and corresponds to `CURLOPT_CURLU`.
+    ///
+    /// Requires libcurl 7.63.0 or later and the `url_7_62_0` feature.
+    #[cfg(feature = "url_7_62_0")]
+    pub fn curlu(&mut self, url: Url) -> Result<(), Error> {
+        let ptr = url::raw(&url);
+        self.inner.curlu = Some(url);
+        self.setopt_ptr(curl_sys::CURLOPT_CURLU, ptr as *const _)
     }
 
     /// Configures the port number to connect to, instead of the one specified
@@ -1324,6 +2082,19 @@ impl<H> Easy2<H> {
         self.setopt_str(curl_sys::CURLOPT_PASSWORD, &pass)
     }
 
+    /// Configures protocol specific options to use when logging in.
+    ///
+    /// This is used by the IMAP, POP3 and SMTP protocols to select the
+    /// authentication mechanism, for example `AUTH=PLAIN` or `AUTH=*` to let
+    /// libcurl pick one.
+    ///
+    /// By default this value is not set and corresponds to
+    /// `CURLOPT_LOGIN_OPTIONS`.
+    pub fn login_options(&mut self, options: &str) -> Result<(), Error> {
+        let options = CString::new(options)?;
+        self.setopt_str(curl_sys::CURLOPT_LOGIN_OPTIONS, &options)
+    }
+
     /// Set HTTP server authentication methods to try
     ///
     /// If more than one method is set, libcurl will first query the site to see
@@ -1538,11 +2309,26 @@ impl<H> Easy2<H> {
     /// By default this option is set to null and corresponds to
     /// `CURLOPT_HTTPPOST`.
     pub fn httppost(&mut self, form: Form) -> Result<(), Error> {
-        self.setopt_ptr(curl_sys::CURLOPT_HTTPPOST, form::raw(&form) as *const _)?;
+        let form = Arc::new(SharedForm(form));
+        self.setopt_ptr(curl_sys::CURLOPT_HTTPPOST, form::raw(&form.0) as *const _)?;
         self.inner.form = Some(form);
         Ok(())
     }
 
+    /// Sets the body of the request to a MIME structure.
+    ///
+    /// For HTTP this makes a `multipart/form-data` POST, while for SMTP and
+    /// IMAP the structure is sent as the message being uploaded. This
+    /// supersedes the `httppost` option.
+    ///
+    /// By default this option is set to null and corresponds to
+    /// `CURLOPT_MIMEPOST`.
+    pub fn mime_post(&mut self, mime: Mime) -> Result<(), Error> {
+        self.setopt_ptr(curl_sys::CURLOPT_MIMEPOST, mime::raw(&mime) as *const _)?;
+        self.inner.mime = Some(mime);
+        Ok(())
+    }
+
     /// Sets the HTTP referer header
     ///
     /// By default this option is not set and corresponds to `CURLOPT_REFERER`.
@@ -1593,15 +2379,41 @@ impl<H> Easy2<H> {
         self.setopt_ptr(curl_sys::CURLOPT_HTTPHEADER, ptr as *const _)
     }
 
-    // /// Add some headers to send to the HTTP proxy.
-    // ///
-    // /// This function is essentially the same as `http_headers`.
-    // ///
-    // /// By default this option is not set and corresponds to
-    // /// `CURLOPT_PROXYHEADER`
-    // pub fn proxy_headers(&mut self, list: &'a List) -> Result<(), Error> {
-    //     self.setopt_ptr(curl_sys::CURLOPT_PROXYHEADER, list.raw as *const _)
-    // }
+    /// Add some headers to send to the HTTP proxy.
+    ///
+    /// This function is essentially the same as `http_headers`, except that
+    /// these headers are only used when `header_opt` is set to send separate
+    /// headers. They are then sent in the CONNECT request used to tunnel
+    /// through a proxy, and alongside `http_headers` in requests sent to a
+    /// plain HTTP proxy.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_PROXYHEADER`
+    pub fn proxy_headers(&mut self, list: List) -> Result<(), Error> {
+        let ptr = list::raw(&list);
+        self.inner.proxy_header_list = Some(list);
+        self.setopt_ptr(curl_sys::CURLOPT_PROXYHEADER, ptr as *const _)
+    }
+
+    /// Configures whether the headers set with `http_headers` are also sent
+    /// to a proxy.
+    ///
+    /// If `separate` is `false` the headers set with `http_headers` are sent
+    /// both to the server and to a proxy, and the ones set with
+    /// `proxy_headers` are ignored. If `separate` is `true` a proxy only gets
+    /// the headers set with `proxy_headers`, so that for example proxy
+    /// credentials aren't passed on to the server and the other way around.
+    ///
+    /// By default this option is `true` as of libcurl 7.42.1, and corresponds
+    /// to `CURLOPT_HEADEROPT`.
+    pub fn header_opt(&mut self, separate: bool) -> Result<(), Error> {
+        let opt = if separate {
+            curl_sys::CURLHEADER_SEPARATE
+        } else {
+            curl_sys::CURLHEADER_UNIFIED
+        };
+        self.setopt_long(curl_sys::CURLOPT_HEADEROPT, opt)
+    }
 
     /// Set the contents of the HTTP Cookie header.
     ///
@@ -1702,23 +2514,77 @@ impl<H> Easy2<H> {
         self.setopt_str(curl_sys::CURLOPT_COOKIELIST, &cookie)
     }
 
-    /// Ask for a HTTP GET request.
+    /// Set the file name of the HSTS cache.
     ///
-    /// By default this option is `false` and corresponds to `CURLOPT_HTTPGET`.
-    pub fn get(&mut self, enable: bool) -> Result<(), Error> {
-        self.setopt_long(curl_sys::CURLOPT_HTTPGET, enable as c_long)
+    /// This enables HSTS, making libcurl switch plain `http://` URLs to
+    /// `https://` for hosts which have previously told it to only be
+    /// accessed over HTTPS with a `Strict-Transport-Security` header. The
+    /// cache is read from this file when HSTS is enabled, and written back
+    /// to it when the handle is dropped unless `HstsCtrl::readonly_file` is
+    /// set.
+    ///
+    /// By default this option is not set and corresponds to `CURLOPT_HSTS`.
+    pub fn hsts_file<P: AsRef<Path>>(&mut self, file: P) -> Result<(), Error> {
+        self.setopt_path(curl_sys::CURLOPT_HSTS, file.as_ref())
     }
 
-    // /// Ask for a HTTP GET request.
-    // ///
-    // /// By default this option is `false` and corresponds to `CURLOPT_HTTPGET`.
-    // pub fn http_version(&mut self, vers: &str) -> Result<(), Error> {
-    //     self.setopt_long(curl_sys::CURLOPT_HTTPGET, enable as c_long)
-    // }
+    /// Configure HSTS for this handle.
+    ///
+    /// HSTS needs to be enabled here for `Handler::hsts_read` and
+    /// `Handler::hsts_write` to be called, unless an HSTS file is set.
+    ///
+    /// By default HSTS is disabled and this corresponds to
+    /// `CURLOPT_HSTS_CTRL`.
+    pub fn hsts_ctrl(&mut self, ctrl: &HstsCtrl) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_HSTS_CTRL, ctrl.bits)
+    }
 
-    /// Ignore the content-length header.
+    /// Set the file name of the Alt-Svc cache.
     ///
-    /// By default this option is `false` and corresponds to
+    /// The cache is read from this file right away, and written back to it
+    /// when the handle is dropped unless `AltSvcCtrl::readonly_file` is set.
+    /// Alternative services learned from `Alt-Svc` response headers over
+    /// HTTPS are then used by later transfers, including those of a handle
+    /// created by a future process using the same file.
+    ///
+    /// This enables Alt-Svc handling, by default accepting alternatives
+    /// using any HTTP version libcurl supports, see `alt_svc_ctrl`.
+    ///
+    /// By default this option is not set and corresponds to `CURLOPT_ALTSVC`.
+    pub fn alt_svc<P: AsRef<Path>>(&mut self, file: P) -> Result<(), Error> {
+        self.setopt_path(curl_sys::CURLOPT_ALTSVC, file.as_ref())
+    }
+
+    /// Configure Alt-Svc handling for this handle.
+    ///
+    /// This enables Alt-Svc handling for the HTTP versions which are set,
+    /// letting libcurl switch to an alternative service using any of them,
+    /// such as upgrading to HTTP/3. An `AltSvcCtrl` with nothing set is
+    /// rejected.
+    ///
+    /// By default Alt-Svc is disabled and this corresponds to
+    /// `CURLOPT_ALTSVC_CTRL`.
+    pub fn alt_svc_ctrl(&mut self, ctrl: &AltSvcCtrl) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_ALTSVC_CTRL, ctrl.bits)
+    }
+
+    /// Ask for a HTTP GET request.
+    ///
+    /// By default this option is `false` and corresponds to `CURLOPT_HTTPGET`.
+    pub fn get(&mut self, enable: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_HTTPGET, enable as c_long)
+    }
+
+    // /// Ask for a HTTP GET request.
+    // ///
+    // /// By default this option is `false` and corresponds to `CURLOPT_HTTPGET`.
+    // pub fn http_version(&mut self, vers: &str) -> Result<(), Error> {
+    //     self.setopt_long(curl_sys::CURLOPT_HTTPGET, enable as c_long)
+    // }
+
+    /// Ignore the content-length header.
+    ///
+    /// By default this option is `false` and corresponds to
     /// `CURLOPT_IGNORE_CONTENT_LENGTH`.
     pub fn ignore_content_length(&mut self, ignore: bool) -> Result<(), Error> {
         self.setopt_long(curl_sys::CURLOPT_IGNORE_CONTENT_LENGTH, ignore as c_long)
@@ -1776,6 +2642,251 @@ impl<H> Easy2<H> {
     // pub fn http_pipewait(&mut self, enable: bool) -> Result<(), Error> {
     // }
 
+    // =========================================================================
+    // SMTP Options
+
+    /// Sets the sender address for an SMTP mail.
+    ///
+    /// The address is sent in the `MAIL FROM` command and should usually be
+    /// enclosed in angle brackets, such as `<sender@example.com>`.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_MAIL_FROM`.
+    pub fn mail_from(&mut self, from: &str) -> Result<(), Error> {
+        let from = CString::new(from)?;
+        self.setopt_str(curl_sys::CURLOPT_MAIL_FROM, &from)
+    }
+
+    /// Sets the list of recipients for an SMTP mail.
+    ///
+    /// Each entry of the list is sent in its own `RCPT TO` command and should
+    /// usually be enclosed in angle brackets, such as `<rcpt@example.com>`.
+    /// When the request isn't sending a mail each entry is instead used as
+    /// the argument of a `VRFY` or `EXPN` command.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_MAIL_RCPT`.
+    pub fn mail_rcpt(&mut self, list: List) -> Result<(), Error> {
+        let ptr = list::raw(&list);
+        self.inner.mail_rcpt_list = Some(list);
+        self.setopt_ptr(curl_sys::CURLOPT_MAIL_RCPT, ptr as *const _)
+    }
+
+    /// Sets the authorization identity for an SMTP mail.
+    ///
+    /// This is sent as the `AUTH` parameter of the `MAIL FROM` command when
+    /// the server supports it, and is used when relaying mail between
+    /// servers. An empty string sends `AUTH=<>`.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_MAIL_AUTH`.
+    pub fn mail_auth(&mut self, auth: &str) -> Result<(), Error> {
+        let auth = CString::new(auth)?;
+        self.setopt_str(curl_sys::CURLOPT_MAIL_AUTH, &auth)
+    }
+
+    /// Configures whether to keep sending a mail when some recipients fail.
+    ///
+    /// Normally a mail is aborted as soon as the server rejects any one of the
+    /// recipients set with `mail_rcpt`. When enabled the mail is still sent
+    /// as long as at least one recipient is accepted.
+    ///
+    /// By default this option is `false` and corresponds to
+    /// `CURLOPT_MAIL_RCPT_ALLOWFAILS`.
+    pub fn mail_rcpt_allowfails(&mut self, allow: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_MAIL_RCPT_ALLOWFAILS, allow as c_long)
+    }
+
+    // =========================================================================
+    // FTP Options
+
+    /// Makes FTP transfers use active mode, connecting back to the given
+    /// address.
+    ///
+    /// The address may be an interface name, an IP address or a host name,
+    /// optionally followed by a colon and a port or range of ports, such as
+    /// `eth0:1024-2048`. The string `-` picks the default address of the
+    /// control connection.
+    ///
+    /// By default this option is not set, so passive mode is used, and
+    /// corresponds to `CURLOPT_FTPPORT`.
+    pub fn ftp_port(&mut self, port: &str) -> Result<(), Error> {
+        let port = CString::new(port)?;
+        self.setopt_str(curl_sys::CURLOPT_FTPPORT, &port)
+    }
+
+    /// Sets a list of FTP commands to send to the server before the transfer.
+    ///
+    /// The commands are sent after logging in, before any directories are
+    /// changed. Prefixing a command with `*` makes libcurl carry on even if
+    /// it fails.
+    ///
+    /// By default this option is not set and corresponds to `CURLOPT_QUOTE`.
+    pub fn quote(&mut self, list: List) -> Result<(), Error> {
+        let ptr = list::raw(&list);
+        self.inner.quote_list = Some(list);
+        self.setopt_ptr(curl_sys::CURLOPT_QUOTE, ptr as *const _)
+    }
+
+    /// Sets a list of FTP commands to send to the server after the transfer.
+    ///
+    /// This function is essentially the same as `quote`, except that the
+    /// commands are only sent if the transfer succeeded.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_POSTQUOTE`.
+    pub fn postquote(&mut self, list: List) -> Result<(), Error> {
+        let ptr = list::raw(&list);
+        self.inner.postquote_list = Some(list);
+        self.setopt_ptr(curl_sys::CURLOPT_POSTQUOTE, ptr as *const _)
+    }
+
+    /// Sets a list of FTP commands to send to the server right before the
+    /// transfer.
+    ///
+    /// This function is essentially the same as `quote`, except that the
+    /// commands are sent once the transfer type has been set, just before the
+    /// command starting the transfer itself.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_PREQUOTE`.
+    pub fn prequote(&mut self, list: List) -> Result<(), Error> {
+        let ptr = list::raw(&list);
+        self.inner.prequote_list = Some(list);
+        self.setopt_ptr(curl_sys::CURLOPT_PREQUOTE, ptr as *const _)
+    }
+
+    /// Configures whether to create the directories of the path which don't
+    /// exist on the server yet when uploading.
+    ///
+    /// By default this option is `FtpCreateMissingDirs::None` and corresponds
+    /// to `CURLOPT_FTP_CREATE_MISSING_DIRS`.
+    pub fn ftp_create_missing_dirs(&mut self, create: FtpCreateMissingDirs) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_FTP_CREATE_MISSING_DIRS, create as c_long)
+    }
+
+    /// Configures whether the `EPSV` command is tried before `PASV` for
+    /// passive mode transfers.
+    ///
+    /// By default this option is `true` and corresponds to
+    /// `CURLOPT_FTP_USE_EPSV`.
+    pub fn ftp_use_epsv(&mut self, enable: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_FTP_USE_EPSV, enable as c_long)
+    }
+
+    /// Configures whether the `EPRT` command is tried before `PORT` for
+    /// active mode transfers.
+    ///
+    /// By default this option is `true` and corresponds to
+    /// `CURLOPT_FTP_USE_EPRT`.
+    pub fn ftp_use_eprt(&mut self, enable: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_FTP_USE_EPRT, enable as c_long)
+    }
+
+    /// Configures how to reach the file on the server.
+    ///
+    /// By default this option is `FtpFileMethod::Default` and corresponds to
+    /// `CURLOPT_FTP_FILEMETHOD`.
+    pub fn ftp_filemethod(&mut self, method: FtpFileMethod) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_FTP_FILEMETHOD, method as c_long)
+    }
+
+    /// Configures whether SSL/TLS is used, upgrading the connection with
+    /// `AUTH TLS` and friends.
+    ///
+    /// Besides FTP this also applies to the other protocols which support
+    /// upgrading a plain connection, such as IMAP, POP3 and SMTP.
+    ///
+    /// By default this option is `FtpSsl::None` and corresponds to
+    /// `CURLOPT_USE_SSL`.
+    pub fn ftp_ssl(&mut self, level: FtpSsl) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_USE_SSL, level as c_long)
+    }
+
+    /// Configures whether to ignore the IP address the server suggests in
+    /// its response to `PASV`, connecting to the control connection's address
+    /// instead.
+    ///
+    /// By default this option is `true` as of libcurl 7.74.0, and corresponds
+    /// to `CURLOPT_FTP_SKIP_PASV_IP`.
+    pub fn ftp_skip_pasv_ip(&mut self, skip: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_FTP_SKIP_PASV_IP, skip as c_long)
+    }
+
+    /// Configures whether a directory listing only contains names.
+    ///
+    /// When the URL refers to a directory this makes libcurl list it with
+    /// `NLST` instead of `LIST`, leaving out file sizes, dates and the like.
+    ///
+    /// By default this option is `false` and corresponds to
+    /// `CURLOPT_DIRLISTONLY`.
+    pub fn dirlistonly(&mut self, enable: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_DIRLISTONLY, enable as c_long)
+    }
+
+    /// Configures whether an upload appends to the file on the server
+    /// instead of overwriting it.
+    ///
+    /// By default this option is `false` and corresponds to
+    /// `CURLOPT_APPEND`.
+    pub fn append(&mut self, append: bool) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_APPEND, append as c_long)
+    }
+
+    // =========================================================================
+    // SSH Options
+
+    /// Set the authentication methods allowed for SFTP and SCP.
+    ///
+    /// By default every method is allowed and this corresponds to
+    /// `CURLOPT_SSH_AUTH_TYPES`.
+    pub fn ssh_auth_types(&mut self, auth: &SshAuth) -> Result<(), Error> {
+        self.setopt_long(curl_sys::CURLOPT_SSH_AUTH_TYPES, auth.bits as c_long)
+    }
+
+    /// Set the file name of the public key used for SSH authentication.
+    ///
+    /// By default libcurl derives it from the private key, falling back to
+    /// `~/.ssh/id_dsa.pub`, and this corresponds to
+    /// `CURLOPT_SSH_PUBLIC_KEYFILE`.
+    pub fn ssh_public_keyfile<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.setopt_path(curl_sys::CURLOPT_SSH_PUBLIC_KEYFILE, path.as_ref())
+    }
+
+    /// Set the file name of the private key used for SSH authentication.
+    ///
+    /// If the key is protected by a passphrase, pass it with `key_password`.
+    ///
+    /// By default libcurl uses `~/.ssh/id_rsa` or `~/.ssh/id_dsa`, and this
+    /// corresponds to `CURLOPT_SSH_PRIVATE_KEYFILE`.
+    pub fn ssh_private_keyfile<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.setopt_path(curl_sys::CURLOPT_SSH_PRIVATE_KEYFILE, path.as_ref())
+    }
+
+    /// Set the known hosts file to check SSH servers' host keys against.
+    ///
+    /// The file should be in the OpenSSH `known_hosts` format. Once it is set
+    /// `Handler::ssh_key` is called to decide what to do with each host key.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_SSH_KNOWNHOSTS`.
+    pub fn ssh_knownhosts<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        self.setopt_path(curl_sys::CURLOPT_SSH_KNOWNHOSTS, path.as_ref())
+    }
+
+    /// Pin the SHA-256 fingerprint of the SSH server's host key.
+    ///
+    /// The fingerprint should be base64 encoded, as shown by
+    /// `ssh-keygen -l`, without the `SHA256:` prefix. The connection is
+    /// refused if the host key doesn't match it.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_SSH_HOST_PUBLIC_KEY_SHA256`.
+    pub fn ssh_host_public_key_sha256(&mut self, fingerprint: &str) -> Result<(), Error> {
+        let fingerprint = CString::new(fingerprint)?;
+        self.setopt_str(curl_sys::CURLOPT_SSH_HOST_PUBLIC_KEY_SHA256, &fingerprint)
+    }
+
     // =========================================================================
     // Protocol Options
 
@@ -2093,6 +3204,37 @@ impl<H> Easy2<H> {
         self.setopt_ptr(curl_sys::CURLOPT_RESOLVE, ptr as *const _)
     }
 
+    /// Specify custom host name to IP address resolves.
+    ///
+    /// This is the same as `resolve`, but takes typed entries instead of
+    /// strings, replacing any list set before.
+    ///
+    /// libcurl loads these entries into its DNS cache when a transfer starts.
+    /// Addresses which change between requests can be supplied by
+    /// `Handler::resolve` instead, or with `connect_to`, which libcurl looks
+    /// at whenever it sets up a connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use curl::easy::{Easy, ResolveEntry};
+    ///
+    /// let mut entry = ResolveEntry::new("www.rust-lang.org", 443);
+    /// entry.add_address([185, 199, 108, 153].into());
+    ///
+    /// let mut handle = Easy::new();
+    /// handle.url("https://www.rust-lang.org/").unwrap();
+    /// handle.resolve_entries(&[entry]).unwrap();
+    /// handle.perform().unwrap();
+    /// ```
+    pub fn resolve_entries(&mut self, entries: &[ResolveEntry]) -> Result<(), Error> {
+        let mut list = List::new();
+        for entry in entries {
+            list.append(&entry.to_string())?;
+        }
+        self.resolve(list)
+    }
+
     /// Configure whether to stop when connected to target server
     ///
     /// When enabled it tells the library to perform all the required proxy
@@ -2107,59 +3249,69 @@ impl<H> Easy2<H> {
         self.setopt_long(curl_sys::CURLOPT_CONNECT_ONLY, enable as c_long)
     }
 
-    // /// Set interface to speak DNS over.
-    // ///
-    // /// Set the name of the network interface that the DNS resolver should bind
-    // /// to. This must be an interface name (not an address).
-    // ///
-    // /// By default this option is not set and corresponds to
-    // /// `CURLOPT_DNS_INTERFACE`.
-    // pub fn dns_interface(&mut self, interface: &str) -> Result<(), Error> {
-    //     let interface = CString::new(interface)?;
-    //     self.setopt_str(curl_sys::CURLOPT_DNS_INTERFACE, &interface)
-    // }
-    //
-    // /// IPv4 address to bind DNS resolves to
-    // ///
-    // /// Set the local IPv4 address that the resolver should bind to. The
-    // /// argument should be of type char * and contain a single numerical IPv4
-    // /// address as a string.
-    // ///
-    // /// By default this option is not set and corresponds to
-    // /// `CURLOPT_DNS_LOCAL_IP4`.
-    // pub fn dns_local_ip4(&mut self, ip: &str) -> Result<(), Error> {
-    //     let ip = CString::new(ip)?;
-    //     self.setopt_str(curl_sys::CURLOPT_DNS_LOCAL_IP4, &ip)
-    // }
-    //
-    // /// IPv6 address to bind DNS resolves to
-    // ///
-    // /// Set the local IPv6 address that the resolver should bind to. The
-    // /// argument should be of type char * and contain a single numerical IPv6
-    // /// address as a string.
-    // ///
-    // /// By default this option is not set and corresponds to
-    // /// `CURLOPT_DNS_LOCAL_IP6`.
-    // pub fn dns_local_ip6(&mut self, ip: &str) -> Result<(), Error> {
-    //     let ip = CString::new(ip)?;
-    //     self.setopt_str(curl_sys::CURLOPT_DNS_LOCAL_IP6, &ip)
-    // }
-    //
-    // /// Set preferred DNS servers.
-    // ///
-    // /// Provides a list of DNS servers to be used instead of the system default.
-    // /// The format of the dns servers option is:
-    // ///
-    // /// ```text
-    // /// host[:port],[host[:port]]...
-    // /// ```
-    // ///
-    // /// By default this option is not set and corresponds to
-    // /// `CURLOPT_DNS_SERVERS`.
-    // pub fn dns_servers(&mut self, servers: &str) -> Result<(), Error> {
-    //     let servers = CString::new(servers)?;
-    //     self.setopt_str(curl_sys::CURLOPT_DNS_SERVERS, &servers)
-    // }
+    /// Attach this handle to a share handle.
+    ///
+    /// Data enabled on the `Share` (such as the DNS cache, cookies or the
+    /// connection pool) will be shared with all other handles attached to the
+    /// same share. This handle keeps the share alive for as long as it is
+    /// attached.
+    ///
+    /// By default this option is not set and corresponds to `CURLOPT_SHARE`.
+    pub fn share(&mut self, share: &Share) -> Result<(), Error> {
+        let ptr = share::raw(share);
+        self.setopt_ptr(curl_sys::CURLOPT_SHARE, ptr as *const _)?;
+        self.inner.share = Some(share.clone());
+        self.set_socket_closer();
+        Ok(())
+    }
+
+    /// Set interface to speak DNS over.
+    ///
+    /// Set the name of the network interface that the DNS resolver should bind
+    /// to. This must be an interface name (not an address).
+    ///
+    /// This option can only be used if libcurl is linked against
+    /// [c-ares](https://c-ares.haxx.se), otherwise setting it will return
+    /// an error.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_DNS_INTERFACE`.
+    pub fn dns_interface(&mut self, interface: &str) -> Result<(), Error> {
+        let interface = CString::new(interface)?;
+        self.setopt_str(curl_sys::CURLOPT_DNS_INTERFACE, &interface)
+    }
+
+    /// IPv4 address to bind DNS resolves to
+    ///
+    /// Set the local IPv4 address that the resolver should bind to. The
+    /// argument should contain a single numerical IPv4 address as a string.
+    ///
+    /// This option can only be used if libcurl is linked against
+    /// [c-ares](https://c-ares.haxx.se), otherwise setting it will return
+    /// an error.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_DNS_LOCAL_IP4`.
+    pub fn dns_local_ip4(&mut self, ip: &str) -> Result<(), Error> {
+        let ip = CString::new(ip)?;
+        self.setopt_str(curl_sys::CURLOPT_DNS_LOCAL_IP4, &ip)
+    }
+
+    /// IPv6 address to bind DNS resolves to
+    ///
+    /// Set the local IPv6 address that the resolver should bind to. The
+    /// argument should contain a single numerical IPv6 address as a string.
+    ///
+    /// This option can only be used if libcurl is linked against
+    /// [c-ares](https://c-ares.haxx.se), otherwise setting it will return
+    /// an error.
+    ///
+    /// By default this option is not set and corresponds to
+    /// `CURLOPT_DNS_LOCAL_IP6`.
+    pub fn dns_local_ip6(&mut self, ip: &str) -> Result<(), Error> {
+        let ip = CString::new(ip)?;
+        self.setopt_str(curl_sys::CURLOPT_DNS_LOCAL_IP6, &ip)
+    }
 
     // =========================================================================
     // SSL/Security Options
@@ -2577,6 +3729,9 @@ impl<H> Easy2<H> {
     /// libcurl will extract lots of information and data about the certificates
     /// in the certificate chain used in the SSL connection.
     ///
+    /// The information can be read with `certinfo_chain` once the transfer
+    /// has completed.
+    ///
     /// By default this option is `false` and corresponds to
     /// `CURLOPT_CERTINFO`.
     pub fn certinfo(&mut self, enable: bool) -> Result<(), Error> {
@@ -3113,6 +4268,79 @@ impl<H> Easy2<H> {
             .map(|c| c as u64)
     }
 
+    /// Get the path the FTP server put us in after logging in
+    ///
+    /// This is typically the home directory of the user, as reported by the
+    /// server in response to `PWD`.
+    ///
+    /// Corresponds to `CURLINFO_FTP_ENTRY_PATH` and may return an error if the
+    /// option isn't supported.
+    pub fn ftp_entry_path(&self) -> Result<Option<&str>, Error> {
+        self.getopt_str(curl_sys::CURLINFO_FTP_ENTRY_PATH)
+    }
+
+    /// Get the timing and size information of the previous transfer at once.
+    ///
+    /// Times are read with microsecond precision, using the `*_TIME_T`
+    /// variants of `CURLINFO_TOTAL_TIME` and friends, which require libcurl
+    /// 7.61.0 or later. This also includes the time spent queued, the time
+    /// spent sending the request, the average speeds and the IDs libcurl
+    /// assigned to the connection and transfer when libcurl provides them.
+    pub fn stats(&self) -> Result<TransferStats, Error> {
+        let time = |opt| {
+            self.getopt_off_t(opt)
+                .map(|us| Duration::from_micros(us as u64))
+        };
+        let size = |opt| self.getopt_off_t(opt).map(|c| c as u64);
+        let id = |opt| match self.getopt_off_t(opt) {
+            Ok(id) if id >= 0 => Some(id as u64),
+            _ => None,
+        };
+        Ok(TransferStats {
+            queue_time: time(curl_sys::CURLINFO_QUEUE_TIME_T).ok(),
+            namelookup_time: time(curl_sys::CURLINFO_NAMELOOKUP_TIME_T)?,
+            connect_time: time(curl_sys::CURLINFO_CONNECT_TIME_T)?,
+            appconnect_time: time(curl_sys::CURLINFO_APPCONNECT_TIME_T)?,
+            pretransfer_time: time(curl_sys::CURLINFO_PRETRANSFER_TIME_T)?,
+            posttransfer_time: time(curl_sys::CURLINFO_POSTTRANSFER_TIME_T).ok(),
+            starttransfer_time: time(curl_sys::CURLINFO_STARTTRANSFER_TIME_T)?,
+            total_time: time(curl_sys::CURLINFO_TOTAL_TIME_T)?,
+            redirect_time: time(curl_sys::CURLINFO_REDIRECT_TIME_T)?,
+            redirect_count: self.redirect_count()?,
+            download_size: size(curl_sys::CURLINFO_SIZE_DOWNLOAD_T)?,
+            upload_size: size(curl_sys::CURLINFO_SIZE_UPLOAD_T)?,
+            download_speed: size(curl_sys::CURLINFO_SPEED_DOWNLOAD_T)?,
+            upload_speed: size(curl_sys::CURLINFO_SPEED_UPLOAD_T)?,
+            header_size: self.header_size()?,
+            request_size: self.request_size()?,
+            num_connects: self.num_connects()?,
+            conn_id: id(curl_sys::CURLINFO_CONN_ID),
+            xfer_id: id(curl_sys::CURLINFO_XFER_ID),
+        })
+    }
+
+    /// Get information about the certificate chain of the last connection.
+    ///
+    /// Returns one `CertInfo` for each certificate in the chain, starting
+    /// with the server's own certificate. `certinfo` must be enabled before
+    /// the transfer for this to return anything, and the chain is empty if
+    /// no TLS connection was made.
+    ///
+    /// Corresponds to the `CURLINFO_CERTINFO` option and may return an error
+    /// if the option isn't supported.
+    pub fn certinfo_chain(&self) -> Result<Vec<CertInfo>, Error> {
+        unsafe {
+            let mut info: *const curl_sys::curl_certinfo = ptr::null();
+            let rc = curl_sys::curl_easy_getinfo(
+                self.inner.handle,
+                curl_sys::CURLINFO_CERTINFO,
+                &mut info,
+            );
+            self.cvt(rc)?;
+            Ok(CertInfo::chain(info))
+        }
+    }
+
     /// Get all known cookies
     ///
     /// Returns a linked-list of all cookies cURL knows (expired ones, too).
@@ -3132,6 +4360,62 @@ impl<H> Easy2<H> {
         }
     }
 
+    /// Get a response header by name.
+    ///
+    /// Looks up the header `name`, case insensitively, among the headers of
+    /// the most recent transfer. If the header was received several times
+    /// `index` picks which one to return, see `Header::amount`. Only headers
+    /// from the given `origin` are considered.
+    ///
+    /// Each transfer may consist of several HTTP requests, for example when
+    /// following redirects. `request` picks which one to look at, starting
+    /// from zero, and `None` means the last one.
+    ///
+    /// Returns `Ok(None)` if no such header was received. Corresponds to
+    /// `curl_easy_header`.
+    #[cfg(feature = "header_7_83_0")]
+    pub fn header(
+        &self,
+        name: &str,
+        index: usize,
+        origin: &HeaderOrigin,
+        request: Option<usize>,
+    ) -> Result<Option<Header>, HeaderError> {
+        let name =
+            CString::new(name).map_err(|_| HeaderError::new(curl_sys::CURLHE_BAD_ARGUMENT))?;
+        unsafe {
+            let mut hout = ptr::null_mut();
+            let rc = curl_sys::curl_easy_header(
+                self.inner.handle,
+                name.as_ptr(),
+                index,
+                header::origin_bits(origin),
+                header::request(request),
+                &mut hout,
+            );
+            match rc {
+                curl_sys::CURLHE_OK => Ok(Some(header::from_raw(&*hout))),
+                curl_sys::CURLHE_MISSING => Ok(None),
+                rc => Err(HeaderError::new(rc)),
+            }
+        }
+    }
+
+    /// Iterate over response headers.
+    ///
+    /// Returns an iterator over all headers from the given `origin` of the
+    /// most recent transfer, in the order they were received. `request`
+    /// picks which HTTP request of the transfer to look at, as with
+    /// `header`.
+    ///
+    /// Corresponds to `curl_easy_nextheader`.
+    #[cfg(feature = "header_7_83_0")]
+    pub fn headers(&mut self, origin: &HeaderOrigin, request: Option<usize>) -> Headers<'_> {
+        // Takes `&mut self` as the iterator holds on to libcurl's header
+        // storage, which is replaced when another transfer is performed.
+        header::headers(self.inner.handle, origin, request)
+    }
+
     /// Wait for pipelining/multiplexing
     ///
     /// Set wait to `true` to tell libcurl to prefer to wait for a connection to
@@ -3193,11 +4477,46 @@ impl<H> Easy2<H> {
     /// call methods like `unpause_write` and `unpause_read` while a transfer is
     /// in progress.
     pub fn perform(&self) -> Result<(), Error> {
+        unsafe { start(self.inner.this)? };
         let ret = unsafe { self.cvt(curl_sys::curl_easy_perform(self.inner.handle)) };
         panic::propagate();
         ret
     }
 
+    /// Performs the transfer described by the options as the returned
+    /// `Reader` is read from, rather than all at once.
+    ///
+    /// The response body is read through the `Read` and `BufRead`
+    /// implementations of the reader instead of being passed to
+    /// `Handler::write`, while all other callbacks still go to the handler.
+    /// The transfer is paused whenever data isn't read as fast as it arrives,
+    /// so the body is never buffered in memory as a whole.
+    ///
+    /// The transfer runs on a private `Multi` handle, and is started by the
+    /// first read. Use `Reader::into_inner` to get this handle back.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::BufRead;
+    ///
+    /// use curl::easy::{Easy2, Handler};
+    ///
+    /// struct Body;
+    ///
+    /// impl Handler for Body {}
+    ///
+    /// let mut easy = Easy2::new(Body);
+    /// easy.url("https://www.rust-lang.org/").unwrap();
+    /// for line in easy.into_reader().lines() {
+    ///     println!("{}", line.unwrap());
+    /// }
+    /// ```
+    pub fn into_reader(mut self) -> Reader<H> {
+        self.inner.reader = Some(ReadBuffer::default());
+        Reader::new(self)
+    }
+
     /// Some protocols have "connection upkeep" mechanisms. These mechanisms
     /// usually send some traffic on existing connections in order to keep them
     /// alive; this can prevent connections from being closed due to overzealous
@@ -3308,48 +4627,95 @@ impl<H> Easy2<H> {
         }
     }
 
-    // TODO: I don't think this is safe, you can drop this which has all the
-    //       callback data and then the next is use-after-free
-    //
-    // /// Attempts to clone this handle, returning a new session handle with the
-    // /// same options set for this handle.
-    // ///
-    // /// Internal state info and things like persistent connections ccannot be
-    // /// transferred.
-    // ///
-    // /// # Errors
-    // ///
-    // /// If a new handle could not be allocated or another error happens, `None`
-    // /// is returned.
-    // pub fn try_clone<'b>(&mut self) -> Option<Easy<'b>> {
-    //     unsafe {
-    //         let handle = curl_sys::curl_easy_duphandle(self.handle);
-    //         if handle.is_null() {
-    //             None
-    //         } else {
-    //             Some(Easy {
-    //                 handle: handle,
-    //                 data: blank_data(),
-    //                 _marker: marker::PhantomData,
-    //             })
-    //         }
-    //     }
-    // }
-
-    /// Receives data from a connected socket.
-    ///
-    /// Only useful after a successful `perform` with the `connect_only` option
-    /// set as well.
-    pub fn recv(&mut self, data: &mut [u8]) -> Result<usize, Error> {
-        unsafe {
-            let mut n = 0;
-            let r = curl_sys::curl_easy_recv(
-                self.inner.handle,
-                data.as_mut_ptr() as *mut _,
-                data.len(),
-                &mut n,
+    /// Attempts to clone this handle, returning a new handle with the same
+    /// options set as this one which reports to `handler` instead.
+    ///
+    /// This corresponds to `curl_easy_duphandle`. Lists, parsed URLs and
+    /// forms set on this handle are carried over, as is an attached `Share`,
+    /// and all callbacks of the new handle go to `handler`. Internal state
+    /// such as persistent connections, the session ID cache and the DNS cache
+    /// is not duplicated, and cookies are only carried over if the cookie
+    /// engine is enabled. A MIME body is copied by libcurl, but handles whose
+    /// MIME body has parts streamed from a reader with
+    /// `MimePart::data_reader` can't be cloned and return an error.
+    ///
+    /// This is useful to configure a template handle once and create handles
+    /// for many requests from it.
+    pub fn try_clone_with<H2: Handler>(&self, handler: H2) -> Result<Easy2<H2>, Error> {
+        // libcurl shares the readers of a duplicated MIME body between both
+        // handles, which can't both read from them.
+        if self.inner.mime.as_ref().is_some_and(mime::has_readers) {
+            let mut err = Error::new(curl_sys::CURLE_BAD_FUNCTION_ARGUMENT);
+            err.set_extra(
+                "cannot clone a handle with a MIME body streamed from a reader".to_string(),
             );
-            if r == curl_sys::CURLE_OK {
+            return Err(err);
+        }
+
+        let mut ret = unsafe {
+            let handle = curl_sys::curl_easy_duphandle(self.inner.handle);
+            if handle.is_null() {
+                return Err(Error::new(curl_sys::CURLE_OUT_OF_MEMORY));
+            }
+            Easy2::from_raw(handle, handler)
+        };
+        ret.bind();
+        ret.fnmatch_function(self.inner.fnmatch)?;
+
+        // The duplicate still points at anything libcurl doesn't copy itself,
+        // so give it its own copies to keep alive.
+        if let Some(list) = &self.inner.header_list {
+            ret.http_headers(list.clone())?;
+        }
+        if let Some(list) = &self.inner.proxy_header_list {
+            ret.proxy_headers(list.clone())?;
+        }
+        if let Some(list) = &self.inner.mail_rcpt_list {
+            ret.mail_rcpt(list.clone())?;
+        }
+        if let Some(list) = &self.inner.quote_list {
+            ret.quote(list.clone())?;
+        }
+        if let Some(list) = &self.inner.postquote_list {
+            ret.postquote(list.clone())?;
+        }
+        if let Some(list) = &self.inner.prequote_list {
+            ret.prequote(list.clone())?;
+        }
+        if let Some(list) = &self.inner.resolve_list {
+            ret.resolve(list.clone())?;
+        } else if self.inner.resolved_list.is_some() {
+            ret.setopt_ptr(curl_sys::CURLOPT_RESOLVE, ptr::null())?;
+        }
+        ret.inner.url = self.inner.url.clone();
+        if let Some(list) = &self.inner.connect_to_list {
+            ret.connect_to(list.clone())?;
+        }
+        #[cfg(feature = "url_7_62_0")]
+        if let Some(url) = &self.inner.curlu {
+            ret.curlu(url.clone())?;
+        }
+        if let Some(share) = &self.inner.share {
+            ret.share(share)?;
+        }
+        ret.inner.form = self.inner.form.clone();
+        Ok(ret)
+    }
+
+    /// Receives data from a connected socket.
+    ///
+    /// Only useful after a successful `perform` with the `connect_only` option
+    /// set as well.
+    pub fn recv(&mut self, data: &mut [u8]) -> Result<usize, Error> {
+        unsafe {
+            let mut n = 0;
+            let r = curl_sys::curl_easy_recv(
+                self.inner.handle,
+                data.as_mut_ptr() as *mut _,
+                data.len(),
+                &mut n,
+            );
+            if r == curl_sys::CURLE_OK {
                 Ok(n)
             } else {
                 Err(Error::new(r))
@@ -3403,6 +4769,55 @@ impl<H> Easy2<H> {
         self.setopt_ptr(opt, val.as_ptr())
     }
 
+    pub(crate) fn read_buffer(&mut self) -> &mut Option<ReadBuffer> {
+        &mut self.inner.reader
+    }
+
+    // The MIME body of this handle, which a `Multi` looks at while the
+    // handle is attached to it.
+    pub(crate) fn mime_ptr(&self) -> *const Option<Mime> {
+        &self.inner.mime
+    }
+
+    // Where the raw handle is stored, so it can be cleared if libcurl takes
+    // care of cleaning it up instead.
+    pub(crate) fn handle_ptr(&mut self) -> *mut *mut curl_sys::CURL {
+        &mut self.inner.handle
+    }
+
+    // Gets the handle ready for a transfer that's about to start.
+    pub(crate) fn start(&mut self) -> Result<(), Error> {
+        unsafe { start(&mut *self.inner) }
+    }
+
+    // The closer of the sockets this handle opens, which a `Multi` keeps
+    // alive while the handle is attached to it.
+    pub(crate) fn sockets(&self) -> Arc<SocketCloser> {
+        self.inner.sockets.clone()
+    }
+
+    // Stops handing sockets opened so far to `Handler::close_socket`, as
+    // they're left behind in the connection cache of a `Multi` this handle
+    // was removed from.
+    pub(crate) fn detach_sockets(&mut self) {
+        let handler = self.inner.sockets.detach();
+        self.inner.sockets = SocketCloser::new(handler);
+        self.set_socket_closer();
+    }
+
+    // Points libcurl at the closer of this handle. Connections shared
+    // through a `Share` may be closed by any handle using it, from any
+    // thread, so their sockets are closed without the handler.
+    fn set_socket_closer(&self) {
+        let data = if self.inner.shares_connections() {
+            ptr::null()
+        } else {
+            Arc::as_ptr(&self.inner.sockets) as *const c_char
+        };
+        self.setopt_ptr(curl_sys::CURLOPT_CLOSESOCKETDATA, data)
+            .expect("failed to set close socket callback");
+    }
+
     fn setopt_ptr(&self, opt: curl_sys::CURLoption, val: *const c_char) -> Result<(), Error> {
         unsafe { self.cvt(curl_sys::curl_easy_setopt(self.inner.handle, opt, val)) }
     }
@@ -3468,6 +4883,15 @@ impl<H> Easy2<H> {
         }
     }
 
+    fn getopt_off_t(&self, opt: curl_sys::CURLINFO) -> Result<curl_sys::curl_off_t, Error> {
+        unsafe {
+            let mut p: curl_sys::curl_off_t = 0;
+            let rc = curl_sys::curl_easy_getinfo(self.inner.handle, opt, &mut p);
+            self.cvt(rc)?;
+            Ok(p)
+        }
+    }
+
     fn getopt_double(&self, opt: curl_sys::CURLINFO) -> Result<c_double, Error> {
         unsafe {
             let mut p = 0 as c_double;
@@ -3528,6 +4952,7 @@ impl<H> Drop for Easy2<H> {
         unsafe {
             curl_sys::curl_easy_cleanup(self.inner.handle);
         }
+        self.inner.sockets.detach();
     }
 }
 
@@ -3557,7 +4982,19 @@ extern "C" fn write_cb<H: Handler>(
 ) -> size_t {
     panic::catch(|| unsafe {
         let input = slice::from_raw_parts(ptr as *const u8, size * nmemb);
-        match (*(data as *mut Inner<H>)).handler.write(input) {
+        let inner = &mut *(data as *mut Inner<H>);
+        let res = match &mut inner.reader {
+            // A `Reader` takes the body instead of the handler.
+            Some(reader) => reader.write(input),
+            #[cfg(feature = "ws_7_86_0")]
+            None => match curl_sys::curl_ws_meta(inner.handle).as_ref() {
+                Some(meta) => inner.handler.ws_write(input, &WsFrame::from_raw(meta)),
+                None => inner.handler.write(input),
+            },
+            #[cfg(not(feature = "ws_7_86_0"))]
+            None => inner.handler.write(input),
+        };
+        match res {
             Ok(s) => s,
             Err(WriteError::Pause) => curl_sys::CURL_WRITEFUNC_PAUSE,
         }
@@ -3618,6 +5055,29 @@ extern "C" fn progress_cb<H: Handler>(
     }
 }
 
+extern "C" fn xferinfo_cb<H: Handler>(
+    data: *mut c_void,
+    dltotal: curl_sys::curl_off_t,
+    dlnow: curl_sys::curl_off_t,
+    ultotal: curl_sys::curl_off_t,
+    ulnow: curl_sys::curl_off_t,
+) -> c_int {
+    let keep_going = panic::catch(|| unsafe {
+        (*(data as *mut Inner<H>)).handler.xferinfo(
+            dltotal as u64,
+            dlnow as u64,
+            ultotal as u64,
+            ulnow as u64,
+        )
+    })
+    .unwrap_or(false);
+    if keep_going {
+        0
+    } else {
+        1
+    }
+}
+
 // TODO: expose `handle`? is that safe?
 extern "C" fn debug_cb<H: Handler>(
     _handle: *mut curl_sys::CURL,
@@ -3660,21 +5120,267 @@ extern "C" fn ssl_ctx_cb<H: Handler>(
     res.unwrap_or(curl_sys::CURLE_SSL_CONNECT_ERROR)
 }
 
-// TODO: expose `purpose` and `sockaddr` inside of `address`
+extern "C" fn hstsread_cb<H: Handler>(
+    _handle: *mut curl_sys::CURL,
+    entry: *mut curl_sys::curl_hstsentry,
+    data: *mut c_void,
+) -> curl_sys::CURLSTScode {
+    panic::catch(|| unsafe {
+        let e = match (*(data as *mut Inner<H>)).handler.hsts_read() {
+            Some(e) => e,
+            None => return curl_sys::CURLSTS_DONE,
+        };
+        let entry = &mut *entry;
+        let expire = e.expire.as_deref().unwrap_or("");
+        if e.host.is_empty()
+            || e.host.len() > entry.namelen
+            || e.host.contains('\0')
+            || expire.len() >= entry.expire.len()
+            || expire.contains('\0')
+        {
+            return curl_sys::CURLSTS_FAIL;
+        }
+        let name = slice::from_raw_parts_mut(entry.name as *mut u8, e.host.len() + 1);
+        name[..e.host.len()].copy_from_slice(e.host.as_bytes());
+        name[e.host.len()] = 0;
+        for (dst, src) in entry.expire.iter_mut().zip(expire.bytes().chain(Some(0))) {
+            *dst = src as c_char;
+        }
+        entry.includeSubDomains = e.include_subdomains as _;
+        curl_sys::CURLSTS_OK
+    })
+    .unwrap_or(curl_sys::CURLSTS_FAIL)
+}
+
+extern "C" fn hstswrite_cb<H: Handler>(
+    _handle: *mut curl_sys::CURL,
+    entry: *mut curl_sys::curl_hstsentry,
+    index: *mut curl_sys::curl_index,
+    data: *mut c_void,
+) -> curl_sys::CURLSTScode {
+    panic::catch(|| unsafe {
+        let entry = &*entry;
+        let host = slice::from_raw_parts(entry.name as *const u8, entry.namelen);
+        let expire = CStr::from_ptr(entry.expire.as_ptr()).to_str().unwrap_or("");
+        let e = HstsEntry {
+            host: String::from_utf8_lossy(host).into_owned(),
+            include_subdomains: entry.includeSubDomains & 1 != 0,
+            expire: match expire {
+                "" | "unlimited" => None,
+                s => Some(s.to_string()),
+            },
+        };
+        let index = &*index;
+        if (*(data as *mut Inner<H>))
+            .handler
+            .hsts_write(&e, index.index, index.total)
+        {
+            curl_sys::CURLSTS_OK
+        } else {
+            curl_sys::CURLSTS_DONE
+        }
+    })
+    .unwrap_or(curl_sys::CURLSTS_FAIL)
+}
+
+extern "C" fn chunk_bgn_cb<H: Handler>(
+    info: *const c_void,
+    data: *mut c_void,
+    remaining: c_int,
+) -> c_long {
+    panic::catch(|| unsafe {
+        let info = FileInfo {
+            raw: &*(info as *const curl_sys::curl_fileinfo),
+        };
+        (*(data as *mut Inner<H>))
+            .handler
+            .chunk_begin(&info, remaining as u32) as c_long
+    })
+    .unwrap_or(curl_sys::CURL_CHUNK_BGN_FUNC_FAIL)
+}
+
+extern "C" fn chunk_end_cb<H: Handler>(data: *mut c_void) -> c_long {
+    let ok =
+        panic::catch(|| unsafe { (*(data as *mut Inner<H>)).handler.chunk_end() }).unwrap_or(false);
+    if ok {
+        curl_sys::CURL_CHUNK_END_FUNC_OK
+    } else {
+        curl_sys::CURL_CHUNK_END_FUNC_FAIL
+    }
+}
+
+extern "C" fn fnmatch_cb<H: Handler>(
+    data: *mut c_void,
+    pattern: *const c_char,
+    name: *const c_char,
+) -> c_int {
+    panic::catch(|| unsafe {
+        let pattern = CStr::from_ptr(pattern).to_bytes();
+        let name = CStr::from_ptr(name).to_bytes();
+        if (*(data as *mut Inner<H>)).handler.fnmatch(pattern, name) {
+            curl_sys::CURL_FNMATCHFUNC_MATCH
+        } else {
+            curl_sys::CURL_FNMATCHFUNC_NOMATCH
+        }
+    })
+    .unwrap_or(curl_sys::CURL_FNMATCHFUNC_FAIL)
+}
+
+extern "C" fn ssh_key_cb<H: Handler>(
+    _handle: *mut curl_sys::CURL,
+    known: *const curl_sys::curl_khkey,
+    found: *const curl_sys::curl_khkey,
+    matched: curl_sys::curl_khmatch,
+    data: *mut c_void,
+) -> c_int {
+    panic::catch(|| unsafe {
+        let matched = match matched {
+            curl_sys::CURLKHMATCH_OK => KhMatch::Ok,
+            curl_sys::CURLKHMATCH_MISMATCH => KhMatch::Mismatch,
+            curl_sys::CURLKHMATCH_MISSING => KhMatch::Missing,
+            _ => return curl_sys::CURLKHSTAT_REJECT as c_int,
+        };
+        let known = known.as_ref().map(|raw| KhKey { raw });
+        let found = KhKey { raw: &*found };
+        (*(data as *mut Inner<H>))
+            .handler
+            .ssh_key(known.as_ref(), &found, matched) as c_int
+    })
+    .unwrap_or(curl_sys::CURLKHSTAT_REJECT as c_int)
+}
+
+// Hands the entries from `Handler::resolve` for the transfer about to start
+// to libcurl, which loads them when the transfer starts.
+unsafe fn start<H>(inner: *mut Inner<H>) -> Result<(), Error> {
+    let inner = match inner.as_mut() {
+        Some(inner) => inner,
+        None => return Ok(()),
+    };
+    #[cfg(feature = "url_7_62_0")]
+    let url = match &inner.curlu {
+        Some(url) => url.url().ok(),
+        None => inner.url.clone(),
+    };
+    #[cfg(not(feature = "url_7_62_0"))]
+    let url = inner.url.clone();
+    let entries = match url {
+        Some(url) => (inner.resolve)(&mut inner.handler, &url),
+        None => return Ok(()),
+    };
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    // Entries set with `resolve` may not have been loaded yet, and libcurl
+    // only keeps a single list.
+    let mut list = inner.resolve_list.clone().unwrap_or_else(List::new);
+    for entry in &entries {
+        list.append(&entry.to_string())?;
+    }
+    crate::cvt(curl_sys::curl_easy_setopt(
+        inner.handle,
+        curl_sys::CURLOPT_RESOLVE,
+        list::raw(&list),
+    ))?;
+    inner.resolved_list = Some(list);
+    Ok(())
+}
+
 extern "C" fn opensocket_cb<H: Handler>(
     data: *mut c_void,
-    _purpose: curl_sys::curlsocktype,
+    purpose: curl_sys::curlsocktype,
     address: *mut curl_sys::curl_sockaddr,
 ) -> curl_sys::curl_socket_t {
     let res = panic::catch(|| unsafe {
-        (*(data as *mut Inner<H>))
+        let inner = &mut *(data as *mut Inner<H>);
+        let purpose = match purpose {
+            curl_sys::CURLSOCKTYPE_ACCEPT => SockType::Accept,
+            _ => SockType::Connection,
+        };
+        // libcurl hands over the address in storage large enough for any
+        // address family, with `addrlen` telling how much of it is used.
+        let addr = SockAddr::try_init(|storage, len| {
+            *len = cmp::min((*address).addrlen as _, *len);
+            ptr::copy_nonoverlapping(
+                ptr::addr_of!((*address).addr) as *const u8,
+                storage as *mut u8,
+                *len as usize,
+            );
+            Ok(())
+        });
+        let socket = inner
             .handler
-            .open_socket((*address).family, (*address).socktype, (*address).protocol)
-            .unwrap_or(curl_sys::CURL_SOCKET_BAD)
+            .open_socket_for(
+                (*address).family,
+                (*address).socktype,
+                (*address).protocol,
+                purpose,
+                addr.ok().and_then(|(_, addr)| addr.as_socket()),
+            )
+            .unwrap_or(curl_sys::CURL_SOCKET_BAD);
+        if socket != curl_sys::CURL_SOCKET_BAD && !inner.shares_connections() {
+            inner.sockets.opened(socket);
+        }
+        socket
     });
     res.unwrap_or(curl_sys::CURL_SOCKET_BAD)
 }
 
+extern "C" fn closesocket_cb(clientp: *mut c_void, socket: curl_sys::curl_socket_t) -> c_int {
+    let res = panic::catch(|| unsafe {
+        match (clientp as *const SocketCloser).as_ref() {
+            Some(closer) => closer.close(socket),
+            None => close_raw_socket(socket),
+        }
+    });
+    match res {
+        Some(()) => 0,
+        None => 1,
+    }
+}
+
+unsafe fn close_socket<H: Handler>(data: *mut c_void, socket: curl_sys::curl_socket_t) {
+    (*(data as *mut Inner<H>)).handler.close_socket(socket)
+}
+
+fn close_raw_socket(socket: curl_sys::curl_socket_t) {
+    drop(unsafe { cvt(socket) });
+
+    #[cfg(unix)]
+    unsafe fn cvt(socket: curl_sys::curl_socket_t) -> Socket {
+        use std::os::unix::prelude::*;
+        Socket::from_raw_fd(socket)
+    }
+
+    #[cfg(windows)]
+    unsafe fn cvt(socket: curl_sys::curl_socket_t) -> Socket {
+        use std::os::windows::prelude::*;
+        Socket::from_raw_socket(socket)
+    }
+}
+
+extern "C" fn sockopt_cb<H: Handler>(
+    data: *mut c_void,
+    socket: curl_sys::curl_socket_t,
+    purpose: curl_sys::curlsocktype,
+) -> c_int {
+    panic::catch(|| unsafe {
+        let purpose = match purpose {
+            curl_sys::CURLSOCKTYPE_IPCXN => SockType::Connection,
+            curl_sys::CURLSOCKTYPE_ACCEPT => SockType::Accept,
+            _ => return curl_sys::CURL_SOCKOPT_OK,
+        };
+        #[cfg(unix)]
+        let socket = std::os::unix::io::BorrowedFd::borrow_raw(socket);
+        #[cfg(windows)]
+        let socket = std::os::windows::io::BorrowedSocket::borrow_raw(socket as _);
+        (*(data as *mut Inner<H>))
+            .handler
+            .sockopt(&SockRef::from(&socket), purpose) as c_int
+    })
+    .unwrap_or(curl_sys::CURL_SOCKOPT_ERROR)
+}
+
 fn double_seconds_to_duration(seconds: f64) -> Duration {
     let whole_seconds = seconds.trunc() as u64;
     let nanos = seconds.fract() * 1_000_000_000f64;
@@ -3930,6 +5636,77 @@ impl fmt::Debug for SslOpt {
     }
 }
 
+impl SshAuth {
+    /// Creates a new set of SSH authentication methods with no members.
+    pub fn new() -> SshAuth {
+        SshAuth { bits: 0 }
+    }
+
+    /// Authenticate with a public/private key pair, see
+    /// `ssh_private_keyfile`.
+    pub fn publickey(&mut self, on: bool) -> &mut SshAuth {
+        self.flag(curl_sys::CURLSSH_AUTH_PUBLICKEY, on)
+    }
+
+    /// Authenticate with a password.
+    pub fn password(&mut self, on: bool) -> &mut SshAuth {
+        self.flag(curl_sys::CURLSSH_AUTH_PASSWORD, on)
+    }
+
+    /// Host based authentication.
+    pub fn host(&mut self, on: bool) -> &mut SshAuth {
+        self.flag(curl_sys::CURLSSH_AUTH_HOST, on)
+    }
+
+    /// Keyboard interactive authentication, answering with the password.
+    pub fn keyboard(&mut self, on: bool) -> &mut SshAuth {
+        self.flag(curl_sys::CURLSSH_AUTH_KEYBOARD, on)
+    }
+
+    /// Authenticate with the keys held by an SSH agent such as `ssh-agent`.
+    pub fn agent(&mut self, on: bool) -> &mut SshAuth {
+        self.flag(curl_sys::CURLSSH_AUTH_AGENT, on)
+    }
+
+    /// GSS-API authentication, such as Kerberos.
+    ///
+    /// This is only supported by the libssh backend of libcurl.
+    pub fn gssapi(&mut self, on: bool) -> &mut SshAuth {
+        self.flag(curl_sys::CURLSSH_AUTH_GSSAPI, on)
+    }
+
+    fn flag(&mut self, bit: c_ulong, on: bool) -> &mut SshAuth {
+        if on {
+            self.bits |= bit;
+        } else {
+            self.bits &= !bit;
+        }
+        self
+    }
+}
+
+impl fmt::Debug for SshAuth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SshAuth")
+            .field(
+                "publickey",
+                &(self.bits & curl_sys::CURLSSH_AUTH_PUBLICKEY != 0),
+            )
+            .field(
+                "password",
+                &(self.bits & curl_sys::CURLSSH_AUTH_PASSWORD != 0),
+            )
+            .field("host", &(self.bits & curl_sys::CURLSSH_AUTH_HOST != 0))
+            .field(
+                "keyboard",
+                &(self.bits & curl_sys::CURLSSH_AUTH_KEYBOARD != 0),
+            )
+            .field("agent", &(self.bits & curl_sys::CURLSSH_AUTH_AGENT != 0))
+            .field("gssapi", &(self.bits & curl_sys::CURLSSH_AUTH_GSSAPI != 0))
+            .finish()
+    }
+}
+
 impl PostRedirections {
     /// Create an empty PostRedirection setting with no flags set.
     pub fn new() -> PostRedirections {
@@ -3992,3 +5769,696 @@ impl fmt::Debug for PostRedirections {
             .finish()
     }
 }
+
+impl HstsCtrl {
+    /// Creates a new set of HSTS options with nothing enabled.
+    pub fn new() -> HstsCtrl {
+        HstsCtrl { bits: 0 }
+    }
+
+    /// Enable HSTS for this handle.
+    ///
+    /// This enables the in-memory HSTS cache, along with the
+    /// `Handler::hsts_read` and `Handler::hsts_write` callbacks.
+    pub fn enable(&mut self, on: bool) -> &mut HstsCtrl {
+        self.flag(curl_sys::CURLHSTS_ENABLE, on)
+    }
+
+    /// Only read the HSTS file, never write the cache back to it.
+    pub fn readonly_file(&mut self, on: bool) -> &mut HstsCtrl {
+        self.flag(curl_sys::CURLHSTS_READONLYFILE, on)
+    }
+
+    fn flag(&mut self, bit: c_long, on: bool) -> &mut HstsCtrl {
+        if on {
+            self.bits |= bit;
+        } else {
+            self.bits &= !bit;
+        }
+        self
+    }
+}
+
+impl fmt::Debug for HstsCtrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HstsCtrl")
+            .field("enable", &(self.bits & curl_sys::CURLHSTS_ENABLE != 0))
+            .field(
+                "readonly_file",
+                &(self.bits & curl_sys::CURLHSTS_READONLYFILE != 0),
+            )
+            .finish()
+    }
+}
+
+impl TransferStats {
+    /// Returns the time spent waiting in a queue before the transfer started,
+    /// if available.
+    ///
+    /// This requires libcurl 8.6.0 or later.
+    pub fn queue_time(&self) -> Option<Duration> {
+        self.queue_time
+    }
+
+    /// Returns the time it took until name resolving was completed.
+    pub fn namelookup_time(&self) -> Duration {
+        self.namelookup_time
+    }
+
+    /// Returns the time it took until the connection to the remote host or
+    /// proxy was completed.
+    pub fn connect_time(&self) -> Duration {
+        self.connect_time
+    }
+
+    /// Returns the time it took until the SSL/SSH connect or handshake was
+    /// completed.
+    pub fn appconnect_time(&self) -> Duration {
+        self.appconnect_time
+    }
+
+    /// Returns the time it took until the transfer was just about to begin.
+    pub fn pretransfer_time(&self) -> Duration {
+        self.pretransfer_time
+    }
+
+    /// Returns the time it took until the request was sent completely, if
+    /// available.
+    ///
+    /// This requires libcurl 8.10.0 or later.
+    pub fn posttransfer_time(&self) -> Option<Duration> {
+        self.posttransfer_time
+    }
+
+    /// Returns the time it took until the first byte was received.
+    pub fn starttransfer_time(&self) -> Duration {
+        self.starttransfer_time
+    }
+
+    /// Returns the total time of the transfer.
+    pub fn total_time(&self) -> Duration {
+        self.total_time
+    }
+
+    /// Returns the time spent on all redirection steps before the final
+    /// transaction was started.
+    pub fn redirect_time(&self) -> Duration {
+        self.redirect_time
+    }
+
+    /// Returns the number of redirects that were followed.
+    pub fn redirect_count(&self) -> u32 {
+        self.redirect_count
+    }
+
+    /// Returns the number of bytes downloaded.
+    pub fn download_size(&self) -> u64 {
+        self.download_size
+    }
+
+    /// Returns the number of bytes uploaded.
+    pub fn upload_size(&self) -> u64 {
+        self.upload_size
+    }
+
+    /// Returns the average download speed, in bytes per second.
+    pub fn download_speed(&self) -> u64 {
+        self.download_speed
+    }
+
+    /// Returns the average upload speed, in bytes per second.
+    pub fn upload_speed(&self) -> u64 {
+        self.upload_speed
+    }
+
+    /// Returns the total size of all received headers, in bytes.
+    pub fn header_size(&self) -> u64 {
+        self.header_size
+    }
+
+    /// Returns the total size of all sent requests, in bytes.
+    pub fn request_size(&self) -> u64 {
+        self.request_size
+    }
+
+    /// Returns the number of new connections that had to be made.
+    pub fn num_connects(&self) -> u64 {
+        self.num_connects
+    }
+
+    /// Returns the ID of the last connection used, if available.
+    ///
+    /// This requires libcurl 8.2.0 or later.
+    pub fn conn_id(&self) -> Option<u64> {
+        self.conn_id
+    }
+
+    /// Returns the ID libcurl assigned to the transfer, if available.
+    ///
+    /// This requires libcurl 8.2.0 or later.
+    pub fn xfer_id(&self) -> Option<u64> {
+        self.xfer_id
+    }
+}
+
+impl<'a> FileInfo<'a> {
+    fn known(&self, flag: c_uint) -> bool {
+        self.raw.flags & flag != 0
+    }
+
+    fn string(&self, ptr: *mut c_char) -> Option<&'a str> {
+        if ptr.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(ptr).to_str().ok() }
+    }
+
+    /// Returns the name of the file.
+    ///
+    /// Returns `None` if the name isn't known or isn't valid utf-8.
+    pub fn filename(&self) -> Option<&'a str> {
+        self.filename_bytes().and_then(|s| str::from_utf8(s).ok())
+    }
+
+    /// Returns the name of the file, in bytes.
+    pub fn filename_bytes(&self) -> Option<&'a [u8]> {
+        if self.raw.filename.is_null() {
+            return None;
+        }
+        unsafe { Some(CStr::from_ptr(self.raw.filename).to_bytes()) }
+    }
+
+    /// Returns the type of the file, if it's known.
+    pub fn file_type(&self) -> Option<FileType> {
+        match self.raw.filetype {
+            curl_sys::CURLFILETYPE_FILE => Some(FileType::File),
+            curl_sys::CURLFILETYPE_DIRECTORY => Some(FileType::Directory),
+            curl_sys::CURLFILETYPE_SYMLINK => Some(FileType::Symlink),
+            curl_sys::CURLFILETYPE_DEVICE_BLOCK => Some(FileType::DeviceBlock),
+            curl_sys::CURLFILETYPE_DEVICE_CHAR => Some(FileType::DeviceChar),
+            curl_sys::CURLFILETYPE_NAMEDPIPE => Some(FileType::NamedPipe),
+            curl_sys::CURLFILETYPE_SOCKET => Some(FileType::Socket),
+            curl_sys::CURLFILETYPE_DOOR => Some(FileType::Door),
+            _ => None,
+        }
+    }
+
+    /// Returns the size of the file in bytes, if it's known.
+    pub fn size(&self) -> Option<u64> {
+        if self.known(curl_sys::CURLFINFOFLAG_KNOWN_SIZE) {
+            Some(self.raw.size as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the permission bits of the file, such as `0o644`, if they're
+    /// known.
+    pub fn perm(&self) -> Option<u32> {
+        if self.known(curl_sys::CURLFINFOFLAG_KNOWN_PERM) {
+            Some(self.raw.perm)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the modification time of the file as it appears in the
+    /// listing, such as `Jan 01 12:00`, if it's known.
+    ///
+    /// libcurl doesn't parse these, as their format depends on the server.
+    pub fn time(&self) -> Option<&'a str> {
+        self.string(self.raw.strings_time)
+    }
+
+    /// Returns the name of the user owning the file, if it's known.
+    pub fn user(&self) -> Option<&'a str> {
+        self.string(self.raw.strings_user)
+    }
+
+    /// Returns the name of the group owning the file, if it's known.
+    pub fn group(&self) -> Option<&'a str> {
+        self.string(self.raw.strings_group)
+    }
+
+    /// Returns the file a symlink points to, if the file is one.
+    pub fn target(&self) -> Option<&'a str> {
+        self.string(self.raw.strings_target)
+    }
+}
+
+impl fmt::Debug for FileInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileInfo")
+            .field("filename", &self.filename())
+            .field("file_type", &self.file_type())
+            .field("size", &self.size())
+            .field("perm", &self.perm())
+            .field("time", &self.time())
+            .finish()
+    }
+}
+
+impl<'a> KhKey<'a> {
+    /// Returns the key.
+    ///
+    /// The key read from the known hosts file is base64 encoded, just as it
+    /// appears there, while the key presented by the server is in its raw
+    /// binary form.
+    pub fn key(&self) -> &'a [u8] {
+        if self.raw.key.is_null() {
+            return &[];
+        }
+        unsafe {
+            if self.raw.len == 0 {
+                CStr::from_ptr(self.raw.key).to_bytes()
+            } else {
+                slice::from_raw_parts(self.raw.key as *const u8, self.raw.len)
+            }
+        }
+    }
+
+    /// Returns the type of the key.
+    pub fn key_type(&self) -> KhType {
+        match self.raw.keytype {
+            curl_sys::CURLKHTYPE_RSA1 => KhType::Rsa1,
+            curl_sys::CURLKHTYPE_RSA => KhType::Rsa,
+            curl_sys::CURLKHTYPE_DSS => KhType::Dss,
+            curl_sys::CURLKHTYPE_ECDSA => KhType::Ecdsa,
+            curl_sys::CURLKHTYPE_ED25519 => KhType::Ed25519,
+            _ => KhType::Unknown,
+        }
+    }
+}
+
+impl fmt::Debug for KhKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KhKey")
+            .field("key", &self.key())
+            .field("key_type", &self.key_type())
+            .finish()
+    }
+}
+
+impl CertInfo {
+    // Reads the chain libcurl returns for `CURLINFO_CERTINFO`, which is null
+    // or has no certificates if no TLS connection was made.
+    unsafe fn chain(info: *const curl_sys::curl_certinfo) -> Vec<CertInfo> {
+        if info.is_null() || (*info).certinfo.is_null() {
+            return Vec::new();
+        }
+        let certs = slice::from_raw_parts((*info).certinfo, (*info).num_of_certs as usize);
+        certs.iter().map(|&list| CertInfo::from_raw(list)).collect()
+    }
+
+    // Each field of a certificate is a `Key:Value` string, where only the
+    // first colon separates the two.
+    unsafe fn from_raw(mut list: *const curl_sys::curl_slist) -> CertInfo {
+        let mut fields = Vec::new();
+        while !list.is_null() {
+            let field = String::from_utf8_lossy(CStr::from_ptr((*list).data).to_bytes());
+            if let Some((key, value)) = field.split_once(':') {
+                fields.push((key.to_string(), value.to_string()));
+            }
+            list = (*list).next;
+        }
+        CertInfo { fields }
+    }
+
+    /// Returns the value of the field `key`, if the certificate has it.
+    ///
+    /// Keys are compared case insensitively.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| &v[..])
+    }
+
+    /// Returns an iterator over all the fields of the certificate, as key and
+    /// value pairs.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (&k[..], &v[..]))
+    }
+
+    /// Returns the subject of the certificate.
+    pub fn subject(&self) -> Option<&str> {
+        self.get("Subject")
+    }
+
+    /// Returns the issuer of the certificate.
+    pub fn issuer(&self) -> Option<&str> {
+        self.get("Issuer")
+    }
+
+    /// Returns the date the certificate is valid from, as formatted by the
+    /// TLS backend.
+    pub fn start_date(&self) -> Option<&str> {
+        self.get("Start date")
+    }
+
+    /// Returns the date the certificate expires, as formatted by the TLS
+    /// backend.
+    pub fn expire_date(&self) -> Option<&str> {
+        self.get("Expire date")
+    }
+
+    /// Returns the certificate itself in PEM format.
+    pub fn cert(&self) -> Option<&str> {
+        self.get("Cert")
+    }
+}
+
+impl AltSvcCtrl {
+    /// Creates a new set of Alt-Svc options with nothing enabled.
+    pub fn new() -> AltSvcCtrl {
+        AltSvcCtrl { bits: 0 }
+    }
+
+    /// Accept alternative services offering HTTP/1.1.
+    pub fn h1(&mut self, on: bool) -> &mut AltSvcCtrl {
+        self.flag(curl_sys::CURLALTSVC_H1, on)
+    }
+
+    /// Accept alternative services offering HTTP/2.
+    pub fn h2(&mut self, on: bool) -> &mut AltSvcCtrl {
+        self.flag(curl_sys::CURLALTSVC_H2, on)
+    }
+
+    /// Accept alternative services offering HTTP/3.
+    ///
+    /// This only has an effect if libcurl is built with HTTP/3 support, see
+    /// `Version::feature_http3`.
+    pub fn h3(&mut self, on: bool) -> &mut AltSvcCtrl {
+        self.flag(curl_sys::CURLALTSVC_H3, on)
+    }
+
+    /// Only read the Alt-Svc file, never write the cache back to it.
+    pub fn readonly_file(&mut self, on: bool) -> &mut AltSvcCtrl {
+        self.flag(curl_sys::CURLALTSVC_READONLYFILE, on)
+    }
+
+    fn flag(&mut self, bit: c_long, on: bool) -> &mut AltSvcCtrl {
+        if on {
+            self.bits |= bit;
+        } else {
+            self.bits &= !bit;
+        }
+        self
+    }
+}
+
+impl fmt::Debug for AltSvcCtrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AltSvcCtrl")
+            .field("h1", &(self.bits & curl_sys::CURLALTSVC_H1 != 0))
+            .field("h2", &(self.bits & curl_sys::CURLALTSVC_H2 != 0))
+            .field("h3", &(self.bits & curl_sys::CURLALTSVC_H3 != 0))
+            .field(
+                "readonly_file",
+                &(self.bits & curl_sys::CURLALTSVC_READONLYFILE != 0),
+            )
+            .finish()
+    }
+}
+
+impl ResolveEntry {
+    /// Creates an entry resolving `host` for connections to `port`, with no
+    /// addresses yet.
+    pub fn new(host: &str, port: u16) -> ResolveEntry {
+        ResolveEntry {
+            host: host.to_string(),
+            port,
+            addresses: Vec::new(),
+            removal: false,
+            transient: false,
+        }
+    }
+
+    /// Creates an entry removing `host` and `port` from the DNS cache, such
+    /// as one added by an earlier transfer.
+    pub fn removal(host: &str, port: u16) -> ResolveEntry {
+        ResolveEntry {
+            removal: true,
+            ..ResolveEntry::new(host, port)
+        }
+    }
+
+    /// Returns the host name this entry is for.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port this entry is for.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the addresses `host` resolves to, in the order they're tried.
+    pub fn addresses(&self) -> &[IpAddr] {
+        &self.addresses
+    }
+
+    /// Returns whether this entry removes the host from the cache.
+    pub fn is_removal(&self) -> bool {
+        self.removal
+    }
+
+    /// Returns whether this entry times out of the cache.
+    pub fn is_transient(&self) -> bool {
+        self.transient
+    }
+
+    /// Adds an address for the host to resolve to.
+    pub fn add_address(&mut self, address: IpAddr) -> &mut ResolveEntry {
+        self.addresses.push(address);
+        self
+    }
+
+    /// Sets whether this entry times out of the cache like a regular
+    /// resolve, instead of staying for as long as the handle is alive.
+    ///
+    /// This requires libcurl 7.75.0 or later.
+    pub fn set_transient(&mut self, on: bool) -> &mut ResolveEntry {
+        self.transient = on;
+        self
+    }
+}
+
+impl fmt::Display for ResolveEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.removal {
+            return write!(f, "-{}:{}", self.host, self.port);
+        }
+        if self.transient {
+            f.write_str("+")?;
+        }
+        write!(f, "{}:{}:", self.host, self.port)?;
+        for (i, address) in self.addresses.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            match address {
+                IpAddr::V4(address) => write!(f, "{}", address)?,
+                IpAddr::V6(address) => write!(f, "[{}]", address)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl HstsEntry {
+    /// Creates a new entry for `host` which never expires and doesn't apply
+    /// to subdomains of `host`.
+    pub fn new(host: &str) -> HstsEntry {
+        HstsEntry {
+            host: host.to_string(),
+            include_subdomains: false,
+            expire: None,
+        }
+    }
+
+    /// Returns the host name this entry is for.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns whether this entry also applies to subdomains of the host.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Returns when this entry expires, in UTC and formatted as
+    /// `YYYYMMDD HH:MM:SS`, or `None` if it never does.
+    pub fn expire(&self) -> Option<&str> {
+        self.expire.as_deref()
+    }
+
+    /// Sets whether this entry also applies to subdomains of the host.
+    pub fn set_include_subdomains(&mut self, on: bool) -> &mut HstsEntry {
+        self.include_subdomains = on;
+        self
+    }
+
+    /// Sets when this entry expires, in UTC and formatted as
+    /// `YYYYMMDD HH:MM:SS`, or `None` for it to never expire.
+    pub fn set_expire(&mut self, expire: Option<&str>) -> &mut HstsEntry {
+        self.expire = expire.map(|s| s.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn khkey(key: &[u8], len: usize, keytype: curl_sys::curl_khtype) -> curl_sys::curl_khkey {
+        curl_sys::curl_khkey {
+            key: key.as_ptr() as *const c_char,
+            len,
+            keytype,
+        }
+    }
+
+    #[test]
+    fn khkey_base64() {
+        // A length of 0 means the key is a nul terminated base64 string.
+        let raw = khkey(b"AAAAC3NzaC1lZDI1NTE5\0", 0, curl_sys::CURLKHTYPE_ED25519);
+        let key = KhKey { raw: &raw };
+        assert_eq!(key.key(), b"AAAAC3NzaC1lZDI1NTE5");
+        assert_eq!(key.key_type(), KhType::Ed25519);
+    }
+
+    #[test]
+    fn khkey_binary() {
+        let data = [0, 0, 0, 7, b's', b's', b'h', 0, 1];
+        let raw = khkey(&data, data.len(), curl_sys::CURLKHTYPE_RSA);
+        let key = KhKey { raw: &raw };
+        assert_eq!(key.key(), data);
+        assert_eq!(key.key_type(), KhType::Rsa);
+    }
+
+    #[test]
+    fn khkey_missing() {
+        let raw = curl_sys::curl_khkey {
+            key: ptr::null(),
+            len: 0,
+            keytype: 100,
+        };
+        let key = KhKey { raw: &raw };
+        assert_eq!(key.key(), b"");
+        assert_eq!(key.key_type(), KhType::Unknown);
+    }
+
+    #[test]
+    fn ssh_key_callback() {
+        #[derive(Default)]
+        struct Keys(Vec<(Option<Vec<u8>>, Vec<u8>, KhMatch)>);
+
+        impl Handler for Keys {
+            fn ssh_key(
+                &mut self,
+                known: Option<&KhKey>,
+                found: &KhKey,
+                matched: KhMatch,
+            ) -> KhStat {
+                let known = known.map(|k| k.key().to_vec());
+                self.0.push((known, found.key().to_vec(), matched));
+                match matched {
+                    KhMatch::Missing => KhStat::FineAddToFile,
+                    _ => KhStat::Reject,
+                }
+            }
+        }
+
+        let mut easy = Easy2::new(Keys::default());
+        let data = &mut *easy.inner as *mut Inner<Keys> as *mut c_void;
+        let known = khkey(b"a25vd24=\0", 0, curl_sys::CURLKHTYPE_RSA);
+        let found = khkey(b"found", 5, curl_sys::CURLKHTYPE_RSA);
+        let call =
+            |known: *const _, matched| ssh_key_cb::<Keys>(easy.raw(), known, &found, matched, data);
+        assert_eq!(
+            call(ptr::null(), curl_sys::CURLKHMATCH_MISSING),
+            curl_sys::CURLKHSTAT_FINE_ADD_TO_FILE as c_int
+        );
+        assert_eq!(
+            call(&known, curl_sys::CURLKHMATCH_MISMATCH),
+            curl_sys::CURLKHSTAT_REJECT as c_int
+        );
+        // Unknown matches are rejected without asking the handler.
+        assert_eq!(call(&known, 100), curl_sys::CURLKHSTAT_REJECT as c_int);
+        assert_eq!(
+            easy.get_ref().0,
+            [
+                (None, b"found".to_vec(), KhMatch::Missing),
+                (
+                    Some(b"a25vd24=".to_vec()),
+                    b"found".to_vec(),
+                    KhMatch::Mismatch
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn certinfo_chain() {
+        let mut server = List::new();
+        server.append("Subject:CN = example.com").unwrap();
+        server.append("Issuer:CN = Example CA").unwrap();
+        server
+            .append("Start date:Jan  1 00:00:00 2024 GMT")
+            .unwrap();
+        server.append("Cert:-----BEGIN CERTIFICATE-----").unwrap();
+        server.append("no separator").unwrap();
+        let mut ca = List::new();
+        ca.append("Subject:CN = Example CA").unwrap();
+        ca.append("Signature Algorithm:").unwrap();
+
+        let mut lists = [list::raw(&server), list::raw(&ca)];
+        let info = curl_sys::curl_certinfo {
+            num_of_certs: 2,
+            certinfo: lists.as_mut_ptr(),
+        };
+        let chain = unsafe { CertInfo::chain(&info) };
+
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].subject(), Some("CN = example.com"));
+        assert_eq!(chain[0].issuer(), Some("CN = Example CA"));
+        assert_eq!(chain[0].get("start DATE"), Some("Jan  1 00:00:00 2024 GMT"));
+        assert_eq!(chain[0].cert(), Some("-----BEGIN CERTIFICATE-----"));
+        assert_eq!(chain[0].expire_date(), None);
+        assert_eq!(chain[0].fields().count(), 4);
+        assert_eq!(chain[1].subject(), Some("CN = Example CA"));
+        assert_eq!(chain[1].get("Signature Algorithm"), Some(""));
+    }
+
+    #[test]
+    fn certinfo_values_with_colons() {
+        let mut cert = List::new();
+        cert.append("Start date:2024-01-01 00:00:00 GMT").unwrap();
+        cert.append("X509v3 Subject Alternative Name:DNS:example.com, DNS:*.example.com")
+            .unwrap();
+
+        let mut lists = [list::raw(&cert)];
+        let info = curl_sys::curl_certinfo {
+            num_of_certs: 1,
+            certinfo: lists.as_mut_ptr(),
+        };
+        let chain = unsafe { CertInfo::chain(&info) };
+
+        assert_eq!(chain[0].start_date(), Some("2024-01-01 00:00:00 GMT"));
+        assert_eq!(
+            chain[0].get("X509v3 Subject Alternative Name"),
+            Some("DNS:example.com, DNS:*.example.com")
+        );
+    }
+
+    #[test]
+    fn certinfo_empty_chain() {
+        assert!(unsafe { CertInfo::chain(ptr::null()) }.is_empty());
+        let info = curl_sys::curl_certinfo {
+            num_of_certs: 0,
+            certinfo: ptr::null_mut(),
+        };
+        assert!(unsafe { CertInfo::chain(&info) }.is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::marker;
+use std::ptr;
+use std::str;
+
+use libc::{c_int, c_uint};
+
+/// A response header parsed by libcurl, returned from `Easy2::header` and
+/// `Easy2::headers`.
+///
+/// This corresponds to the `curl_header` struct in libcurl. Folded header
+/// lines are joined into a single value, and headers which appear several
+/// times in the same response are told apart with `index` and `amount`.
+#[derive(Clone)]
+pub struct Header {
+    name: String,
+    value: Vec<u8>,
+    amount: usize,
+    index: usize,
+    origin: HeaderOrigin,
+}
+
+/// The origins of response headers to look at, and the origin of a `Header`.
+///
+/// A header is either a regular response header, a trailer, a header from a
+/// proxy CONNECT response, a header from an informational 1xx response, or
+/// an HTTP/2 or HTTP/3 pseudo header.
+#[derive(Clone, Default)]
+pub struct HeaderOrigin {
+    bits: c_uint,
+}
+
+/// An iterator over response headers, returned from `Easy2::headers`.
+///
+/// This corresponds to `curl_easy_nextheader` in libcurl.
+pub struct Headers<'a> {
+    handle: *mut curl_sys::CURL,
+    origin: c_uint,
+    request: c_int,
+    prev: *mut curl_sys::curl_header,
+    _marker: marker::PhantomData<&'a mut curl_sys::CURL>,
+}
+
+pub fn from_raw(raw: &curl_sys::curl_header) -> Header {
+    unsafe {
+        Header {
+            name: String::from_utf8_lossy(CStr::from_ptr(raw.name).to_bytes()).into_owned(),
+            value: CStr::from_ptr(raw.value).to_bytes().to_vec(),
+            amount: raw.amount,
+            index: raw.index,
+            origin: HeaderOrigin { bits: raw.origin },
+        }
+    }
+}
+
+pub fn request(request: Option<usize>) -> c_int {
+    match request {
+        Some(n) => n as c_int,
+        None => -1,
+    }
+}
+
+pub fn headers<'a>(
+    handle: *mut curl_sys::CURL,
+    origin: &HeaderOrigin,
+    request: Option<usize>,
+) -> Headers<'a> {
+    Headers {
+        handle,
+        origin: origin.bits,
+        request: self::request(request),
+        prev: ptr::null_mut(),
+        _marker: marker::PhantomData,
+    }
+}
+
+pub fn origin_bits(origin: &HeaderOrigin) -> c_uint {
+    origin.bits
+}
+
+impl Header {
+    /// Returns the name of this header, in the case it was received with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of this header, with leading and trailing
+    /// whitespace removed.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Returns the value of this header as a string, if it is valid UTF-8.
+    pub fn value_str(&self) -> Option<&str> {
+        str::from_utf8(&self.value).ok()
+    }
+
+    /// Returns the number of headers with this name received in the same
+    /// response.
+    pub fn amount(&self) -> usize {
+        self.amount
+    }
+
+    /// Returns the index of this header among all headers with the same name
+    /// in the response, starting from zero.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns where this header came from.
+    pub fn origin(&self) -> &HeaderOrigin {
+        &self.origin
+    }
+}
+
+impl fmt::Debug for Header {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Header")
+            .field("name", &self.name)
+            .field("value", &String::from_utf8_lossy(&self.value))
+            .field("amount", &self.amount)
+            .field("index", &self.index)
+            .field("origin", &self.origin)
+            .finish()
+    }
+}
+
+impl HeaderOrigin {
+    /// Creates a new set of origins with nothing selected.
+    pub fn new() -> HeaderOrigin {
+        HeaderOrigin { bits: 0 }
+    }
+
+    /// Regular headers of the final response.
+    pub fn header(&mut self, on: bool) -> &mut HeaderOrigin {
+        self.flag(curl_sys::CURLH_HEADER, on)
+    }
+
+    /// Trailers sent after the response body.
+    pub fn trailer(&mut self, on: bool) -> &mut HeaderOrigin {
+        self.flag(curl_sys::CURLH_TRAILER, on)
+    }
+
+    /// Headers of the response to a proxy CONNECT request.
+    pub fn connect(&mut self, on: bool) -> &mut HeaderOrigin {
+        self.flag(curl_sys::CURLH_CONNECT, on)
+    }
+
+    /// Headers of informational 1xx responses preceding the final one.
+    pub fn informational(&mut self, on: bool) -> &mut HeaderOrigin {
+        self.flag(curl_sys::CURLH_1XX, on)
+    }
+
+    /// HTTP/2 and HTTP/3 pseudo headers, such as `:status`.
+    pub fn pseudo(&mut self, on: bool) -> &mut HeaderOrigin {
+        self.flag(curl_sys::CURLH_PSEUDO, on)
+    }
+
+    /// Returns whether regular headers are included.
+    pub fn is_header(&self) -> bool {
+        self.bits & curl_sys::CURLH_HEADER != 0
+    }
+
+    /// Returns whether trailers are included.
+    pub fn is_trailer(&self) -> bool {
+        self.bits & curl_sys::CURLH_TRAILER != 0
+    }
+
+    /// Returns whether proxy CONNECT response headers are included.
+    pub fn is_connect(&self) -> bool {
+        self.bits & curl_sys::CURLH_CONNECT != 0
+    }
+
+    /// Returns whether informational 1xx response headers are included.
+    pub fn is_informational(&self) -> bool {
+        self.bits & curl_sys::CURLH_1XX != 0
+    }
+
+    /// Returns whether pseudo headers are included.
+    pub fn is_pseudo(&self) -> bool {
+        self.bits & curl_sys::CURLH_PSEUDO != 0
+    }
+
+    fn flag(&mut self, bit: c_uint, on: bool) -> &mut HeaderOrigin {
+        if on {
+            self.bits |= bit;
+        } else {
+            self.bits &= !bit;
+        }
+        self
+    }
+}
+
+impl fmt::Debug for HeaderOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HeaderOrigin")
+            .field("header", &self.is_header())
+            .field("trailer", &self.is_trailer())
+            .field("connect", &self.is_connect())
+            .field("informational", &self.is_informational())
+            .field("pseudo", &self.is_pseudo())
+            .finish()
+    }
+}
+
+impl<'a> Iterator for Headers<'a> {
+    type Item = Header;
+
+    fn next(&mut self) -> Option<Header> {
+        unsafe {
+            let next =
+                curl_sys::curl_easy_nextheader(self.handle, self.origin, self.request, self.prev);
+            if next.is_null() {
+                return None;
+            }
+            self.prev = next;
+            Some(from_raw(&*next))
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Headers<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Headers").finish()
+    }
+}
@@ -62,6 +62,22 @@ impl fmt::Debug for List {
     }
 }
 
+impl Clone for List {
+    fn clone(&self) -> List {
+        let mut ret = List::new();
+        let mut cur = self.raw;
+        unsafe {
+            while !cur.is_null() {
+                let raw = curl_sys::curl_slist_append(ret.raw, (*cur).data);
+                assert!(!raw.is_null());
+                ret.raw = raw;
+                cur = (*cur).next;
+            }
+        }
+        ret
+    }
+}
+
 impl<'a> IntoIterator for &'a List {
     type IntoIter = Iter<'a>;
     type Item = &'a [u8];
@@ -0,0 +1,312 @@
+use std::ffi::CString;
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom};
+use std::mem;
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+use libc::{c_char, c_int, c_void, size_t};
+
+use crate::easy::{list, List};
+use crate::panic;
+use crate::Error;
+
+/// A MIME structure, used for multipart uploads.
+///
+/// This corresponds to the `curl_mime` type in libcurl. Parts are added with
+/// `add_part` and configured through the returned `MimePart`, after which the
+/// whole structure is handed off to `Easy2::mime_post` to be sent as a
+/// `multipart/form-data` HTTP POST, or as the body of an SMTP or IMAP
+/// message.
+///
+/// This is the replacement for the `Form` type, which is built on the
+/// deprecated `curl_formadd` interface. Requires libcurl 7.56.0 or later.
+///
+/// # Examples
+///
+/// ```
+/// use curl::easy::{Easy, Mime};
+///
+/// let mut mime = Mime::new();
+/// {
+///     let mut part = mime.add_part();
+///     part.name("greeting").unwrap();
+///     part.data(b"hello").unwrap();
+/// }
+/// {
+///     let mut part = mime.add_part();
+///     part.name("file").unwrap();
+///     part.filename("data.txt").unwrap();
+///     part.content_type("text/plain").unwrap();
+///     part.data_reader(Some(5), std::io::Cursor::new(b"world".to_vec())).unwrap();
+/// }
+///
+/// let mut easy = Easy::new();
+/// easy.mime_post(mime).unwrap();
+/// ```
+pub struct Mime {
+    raw: *mut curl_sys::curl_mime,
+    // The readers of parts, possibly nested, which stream their data from
+    // one. These are owned here rather than by libcurl, as libcurl copies
+    // them along with the structure when duplicating a handle and would free
+    // them once for each copy.
+    readers: Vec<OwnedReader>,
+}
+
+struct OwnedReader {
+    data: *mut c_void,
+    free: unsafe fn(*mut c_void),
+}
+
+/// One part of a `Mime` structure, returned from `Mime::add_part`.
+///
+/// Setters on this type configure the part in place. The part lives as long
+/// as the `Mime` it was added to.
+pub struct MimePart<'a> {
+    raw: *mut curl_sys::curl_mimepart,
+    readers: &'a mut Vec<OwnedReader>,
+}
+
+pub fn raw(mime: &Mime) -> *mut curl_sys::curl_mime {
+    mime.raw
+}
+
+pub fn has_readers(mime: &Mime) -> bool {
+    !mime.readers.is_empty()
+}
+
+// Any data sources attached to parts are required to be `Send`.
+unsafe impl Send for Mime {}
+
+impl Mime {
+    /// Creates a new, empty MIME structure.
+    pub fn new() -> Mime {
+        crate::init();
+        unsafe {
+            let raw = curl_sys::curl_mime_init(ptr::null_mut());
+            assert!(!raw.is_null());
+            Mime {
+                raw,
+                readers: Vec::new(),
+            }
+        }
+    }
+
+    /// Appends a new, empty part to this structure and returns it.
+    pub fn add_part(&mut self) -> MimePart<'_> {
+        unsafe {
+            let raw = curl_sys::curl_mime_addpart(self.raw);
+            assert!(!raw.is_null());
+            MimePart {
+                raw,
+                readers: &mut self.readers,
+            }
+        }
+    }
+}
+
+impl Default for Mime {
+    fn default() -> Mime {
+        Mime::new()
+    }
+}
+
+impl fmt::Debug for Mime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mime").field("parts", &"...").finish()
+    }
+}
+
+impl Drop for Mime {
+    fn drop(&mut self) {
+        // The readers are dropped after this, once libcurl can no longer use
+        // them.
+        unsafe { curl_sys::curl_mime_free(self.raw) }
+    }
+}
+
+impl Drop for OwnedReader {
+    fn drop(&mut self) {
+        unsafe { (self.free)(self.data) }
+    }
+}
+
+impl<'a> MimePart<'a> {
+    /// Sets the name of this part, used as the field name in a
+    /// `Content-Disposition` header of a form.
+    pub fn name(&mut self, name: &str) -> Result<(), Error> {
+        let name = CString::new(name)?;
+        unsafe { crate::cvt(curl_sys::curl_mime_name(self.raw, name.as_ptr())) }
+    }
+
+    /// Sets the remote file name of this part.
+    ///
+    /// This is what makes a part a file upload in a form. It does not cause
+    /// any data to be read, see `file_data` for that.
+    pub fn filename(&mut self, filename: &str) -> Result<(), Error> {
+        let filename = CString::new(filename)?;
+        unsafe { crate::cvt(curl_sys::curl_mime_filename(self.raw, filename.as_ptr())) }
+    }
+
+    /// Sets the content type of this part, for example `image/png`.
+    pub fn content_type(&mut self, content_type: &str) -> Result<(), Error> {
+        let content_type = CString::new(content_type)?;
+        unsafe { crate::cvt(curl_sys::curl_mime_type(self.raw, content_type.as_ptr())) }
+    }
+
+    /// Sets the transfer encoding to apply to the data of this part.
+    ///
+    /// Supported encodings are `binary`, `8bit`, `7bit`, `base64` and
+    /// `quoted-printable`. The data is encoded by libcurl as it is sent, and
+    /// a matching `Content-Transfer-Encoding` header is added to the part.
+    pub fn encoder(&mut self, encoding: &str) -> Result<(), Error> {
+        let encoding = CString::new(encoding)?;
+        unsafe { crate::cvt(curl_sys::curl_mime_encoder(self.raw, encoding.as_ptr())) }
+    }
+
+    /// Sets the contents of this part from a buffer of bytes.
+    ///
+    /// The data is copied by libcurl.
+    pub fn data(&mut self, data: &[u8]) -> Result<(), Error> {
+        unsafe {
+            crate::cvt(curl_sys::curl_mime_data(
+                self.raw,
+                data.as_ptr() as *const c_char,
+                data.len() as size_t,
+            ))
+        }
+    }
+
+    /// Sets the contents of this part to be read from a file.
+    ///
+    /// The file is read when the transfer is performed. This also sets the
+    /// remote file name of the part to the base name of `path`, which can
+    /// be overridden afterwards with `filename`.
+    pub fn file_data<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path2cstr(path.as_ref())?;
+        unsafe { crate::cvt(curl_sys::curl_mime_filedata(self.raw, path.as_ptr())) }
+    }
+
+    /// Sets the contents of this part to be streamed from a reader.
+    ///
+    /// If `size` is `None` the length of the data is unknown, in which case
+    /// HTTP uploads will use chunked transfer encoding. The reader must be
+    /// seekable so that libcurl can rewind it when a request needs to be
+    /// sent again, for example after a redirect or an authentication
+    /// challenge.
+    pub fn data_reader<R>(&mut self, size: Option<u64>, reader: R) -> Result<(), Error>
+    where
+        R: Read + Seek + Send + 'static,
+    {
+        let size = match size {
+            Some(size) => size as curl_sys::curl_off_t,
+            None => -1,
+        };
+        let reader = OwnedReader {
+            data: Box::into_raw(Box::new(reader)) as *mut c_void,
+            free: free::<R>,
+        };
+        unsafe {
+            crate::cvt(curl_sys::curl_mime_data_cb(
+                self.raw,
+                size,
+                read_cb::<R>,
+                Some(seek_cb::<R>),
+                None,
+                reader.data,
+            ))?;
+        }
+        self.readers.push(reader);
+        Ok(())
+    }
+
+    /// Sets the contents of this part to a nested multipart structure.
+    ///
+    /// The `Mime` is consumed and becomes owned by this part. The content type
+    /// of this part defaults to `multipart/mixed` unless set otherwise.
+    pub fn subparts(&mut self, mut mime: Mime) -> Result<(), Error> {
+        unsafe {
+            crate::cvt(curl_sys::curl_mime_subparts(self.raw, mime.raw))?;
+        }
+        self.readers.append(&mut mime.readers);
+        mem::forget(mime);
+        Ok(())
+    }
+
+    /// Sets custom headers for this part.
+    ///
+    /// Headers are sent in addition to the ones generated by libcurl. The
+    /// `List` is consumed and becomes owned by this part.
+    pub fn headers(&mut self, headers: List) -> Result<(), Error> {
+        unsafe {
+            crate::cvt(curl_sys::curl_mime_headers(
+                self.raw,
+                list::raw(&headers),
+                1,
+            ))?;
+        }
+        mem::forget(headers);
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Debug for MimePart<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MimePart").finish()
+    }
+}
+
+#[cfg(unix)]
+fn path2cstr(p: &Path) -> Result<CString, Error> {
+    use std::os::unix::prelude::*;
+    Ok(CString::new(p.as_os_str().as_bytes())?)
+}
+
+#[cfg(windows)]
+fn path2cstr(p: &Path) -> Result<CString, Error> {
+    match p.to_str() {
+        Some(s) => Ok(CString::new(s)?),
+        None => Err(Error::new(curl_sys::CURLE_CONV_FAILED)),
+    }
+}
+
+extern "C" fn read_cb<R: Read>(
+    ptr: *mut c_char,
+    size: size_t,
+    nmemb: size_t,
+    data: *mut c_void,
+) -> size_t {
+    panic::catch(|| unsafe {
+        let input = slice::from_raw_parts_mut(ptr as *mut u8, size * nmemb);
+        match (*(data as *mut R)).read(input) {
+            Ok(n) => n,
+            Err(_) => curl_sys::CURL_READFUNC_ABORT,
+        }
+    })
+    .unwrap_or(curl_sys::CURL_READFUNC_ABORT)
+}
+
+extern "C" fn seek_cb<R: Seek>(
+    data: *mut c_void,
+    offset: curl_sys::curl_off_t,
+    origin: c_int,
+) -> c_int {
+    panic::catch(|| unsafe {
+        let from = match origin {
+            libc::SEEK_SET => SeekFrom::Start(offset as u64),
+            libc::SEEK_CUR => SeekFrom::Current(offset),
+            libc::SEEK_END => SeekFrom::End(offset),
+            _ => return curl_sys::CURL_SEEKFUNC_CANTSEEK,
+        };
+        match (*(data as *mut R)).seek(from) {
+            Ok(_) => curl_sys::CURL_SEEKFUNC_OK,
+            Err(_) => curl_sys::CURL_SEEKFUNC_FAIL,
+        }
+    })
+    .unwrap_or(curl_sys::CURL_SEEKFUNC_FAIL)
+}
+
+unsafe fn free<R>(data: *mut c_void) {
+    drop(Box::from_raw(data as *mut R));
+}
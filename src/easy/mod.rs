@@ -10,13 +10,33 @@
 mod form;
 mod handle;
 mod handler;
+#[cfg(feature = "header_7_83_0")]
+mod header;
 mod list;
+pub(crate) mod mime;
+mod reader;
+mod share;
 mod windows;
+#[cfg(feature = "ws_7_86_0")]
+mod ws;
 
 pub use self::form::{Form, Part};
 pub use self::handle::{Easy, Transfer};
-pub use self::handler::{Auth, NetRc, PostRedirections, ProxyType, SslOpt};
+pub(crate) use self::handler::SocketCloser;
+pub use self::handler::{AltSvcCtrl, CertInfo, HstsCtrl, HstsEntry, TransferStats};
+pub use self::handler::{Auth, NetRc, PostRedirections, ProxyType, ResolveEntry, SslOpt};
+pub use self::handler::{ChunkAction, FileInfo, FileType};
 pub use self::handler::{Easy2, Handler};
+pub use self::handler::{FtpCreateMissingDirs, FtpFileMethod, FtpSsl};
 pub use self::handler::{HttpVersion, IpResolve, SslVersion, TimeCondition};
 pub use self::handler::{InfoType, ReadError, SeekResult, WriteError};
+pub use self::handler::{KhKey, KhMatch, KhStat, KhType, SshAuth};
+pub use self::handler::{SockOptResult, SockType};
+#[cfg(feature = "header_7_83_0")]
+pub use self::header::{Header, HeaderOrigin, Headers};
 pub use self::list::{Iter, List};
+pub use self::mime::{Mime, MimePart};
+pub use self::reader::{EasyReader, Reader};
+pub use self::share::Share;
+#[cfg(feature = "ws_7_86_0")]
+pub use self::ws::{WebSocket, WsFlags, WsFrame};
@@ -0,0 +1,309 @@
+use std::cmp;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+use std::ptr;
+use std::time::Duration;
+
+use crate::easy::{Easy, Easy2, WriteError};
+use crate::multi::Multi;
+use crate::{Error, MultiError};
+
+/// A reader over the body of a response, created with `Easy2::into_reader`.
+///
+/// Reading from this type drives the transfer on a private `Multi` handle
+/// until more of the body is available. Data is handed out in the chunks
+/// libcurl writes it in, and the transfer is paused until a chunk has been
+/// read completely.
+///
+/// An error of the transfer is returned from the read which would have
+/// returned the data after it, and reads return 0 once the transfer has
+/// finished.
+pub struct Reader<H> {
+    // Safety: This *must* be before `easy` as it must be dropped first.
+    driver: Driver,
+    easy: Easy2<H>,
+}
+
+/// A reader over the body of a response, created with `Easy::reader`.
+///
+/// This is the same as `Reader` except that it borrows an `Easy` handle,
+/// which can be used for further transfers once the reader is dropped.
+pub struct EasyReader<'easy> {
+    driver: Driver,
+    easy: &'easy mut Easy,
+}
+
+// Buffers the body for a reader in place of the handler, holding a single
+// write of libcurl at a time.
+#[derive(Default)]
+pub(crate) struct ReadBuffer {
+    data: Vec<u8>,
+    pos: usize,
+    paused: bool,
+}
+
+// Drives a single easy handle on a private multi handle.
+struct Driver {
+    multi: Multi,
+    easy: *mut curl_sys::CURL,
+    error: Option<Error>,
+    done: bool,
+}
+
+// The multi handle and easy handle are only ever used by whoever is reading,
+// and libcurl handles may move between threads as long as they aren't used
+// concurrently.
+unsafe impl<H: Send> Send for Reader<H> {}
+unsafe impl Send for EasyReader<'_> {}
+
+impl<H> Reader<H> {
+    pub(crate) fn new(mut easy: Easy2<H>) -> Reader<H> {
+        let mut driver = Driver::new(easy.raw());
+        if let Err(e) = easy.start() {
+            driver.fail(e);
+        }
+        Reader { driver, easy }
+    }
+
+    /// Acquires a reference to the easy handle performing the transfer.
+    ///
+    /// This can be used to look at information about the transfer such as
+    /// `response_code` while the body is being read.
+    pub fn get_ref(&self) -> &Easy2<H> {
+        &self.easy
+    }
+
+    /// Returns the easy handle, aborting the transfer if it hasn't finished
+    /// yet.
+    ///
+    /// Data written by libcurl goes to `Handler::write` again afterwards.
+    pub fn into_inner(self) -> Easy2<H> {
+        let Reader {
+            mut driver,
+            mut easy,
+        } = self;
+        driver.detach();
+        *easy.read_buffer() = None;
+        easy
+    }
+}
+
+impl<H> Read for Reader<H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        read(self, buf)
+    }
+}
+
+impl<H> BufRead for Reader<H> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.driver.fill(&mut self.easy)?;
+        Ok(buffer(&mut self.easy).data())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        buffer(&mut self.easy).consume(amt)
+    }
+}
+
+impl<H: fmt::Debug> fmt::Debug for Reader<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Reader")
+            .field("easy", &self.easy)
+            .field("done", &self.driver.done)
+            .finish()
+    }
+}
+
+impl<'easy> EasyReader<'easy> {
+    pub(crate) fn new(easy: &'easy mut Easy) -> EasyReader<'easy> {
+        *easy.inner_mut().read_buffer() = Some(ReadBuffer::default());
+        EasyReader {
+            driver: Driver::new(easy.raw()),
+            easy,
+        }
+    }
+
+    /// Acquires a reference to the easy handle performing the transfer.
+    ///
+    /// This can be used to look at information about the transfer such as
+    /// `response_code` while the body is being read.
+    pub fn get_ref(&self) -> &Easy {
+        self.easy
+    }
+}
+
+impl Read for EasyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        read(self, buf)
+    }
+}
+
+impl BufRead for EasyReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.driver.fill(self.easy.inner_mut())?;
+        Ok(buffer(self.easy.inner_mut()).data())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        buffer(self.easy.inner_mut()).consume(amt)
+    }
+}
+
+impl fmt::Debug for EasyReader<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EasyReader")
+            .field("easy", &self.easy)
+            .field("done", &self.driver.done)
+            .finish()
+    }
+}
+
+impl Drop for EasyReader<'_> {
+    fn drop(&mut self) {
+        self.driver.detach();
+        *self.easy.inner_mut().read_buffer() = None;
+    }
+}
+
+impl ReadBuffer {
+    // Takes a write of libcurl, pausing the transfer while the previous one
+    // hasn't been read completely.
+    pub(crate) fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+        if self.pos < self.data.len() {
+            self.paused = true;
+            return Err(WriteError::Pause);
+        }
+        self.data.clear();
+        self.data.extend_from_slice(data);
+        self.pos = 0;
+        Ok(data.len())
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data[self.pos..]
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.data.len());
+    }
+}
+
+impl Driver {
+    fn new(easy: *mut curl_sys::CURL) -> Driver {
+        let multi = Multi::new();
+        // Adding the handle right away rather than on the first read makes
+        // sure it is never performed on its own with writes being paused.
+        let res = unsafe { cvt(curl_sys::curl_multi_add_handle(multi.raw(), easy)) };
+        let (easy, error) = match res {
+            Ok(()) => (easy, None),
+            Err(e) => (ptr::null_mut(), Some(multi_error(e))),
+        };
+        Driver {
+            multi,
+            easy,
+            done: error.is_some(),
+            error,
+        }
+    }
+
+    // Fails the transfer before it has started.
+    fn fail(&mut self, error: Error) {
+        self.detach();
+        self.error.get_or_insert(error);
+        self.done = true;
+    }
+
+    // Makes progress on the transfer until there's data to read or it has
+    // finished.
+    fn fill<H>(&mut self, easy: &mut Easy2<H>) -> io::Result<()> {
+        loop {
+            let buf = buffer(easy);
+            if !buf.data().is_empty() {
+                return Ok(());
+            }
+            if let Some(e) = self.error.take() {
+                return Err(e.into());
+            }
+            if self.done {
+                return Ok(());
+            }
+            if buf.paused {
+                buf.paused = false;
+                easy.unpause_write()?;
+                continue;
+            }
+
+            self.multi.perform()?;
+            crate::panic::propagate();
+            let mut result = None;
+            self.multi.messages(|msg| {
+                if let Some(res) = msg.result() {
+                    result = Some(res);
+                }
+            });
+            match result {
+                Some(Ok(())) => self.done = true,
+                Some(Err(mut e)) => {
+                    if let Some(s) = easy.take_error_buf() {
+                        e.set_extra(s);
+                    }
+                    self.error = Some(e);
+                    self.done = true;
+                }
+                None if buffer(easy).data().is_empty() && !buffer(easy).paused => {
+                    self.multi.wait(&mut [], Duration::from_secs(1))?;
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn detach(&mut self) {
+        if !self.easy.is_null() {
+            unsafe {
+                let _ = cvt(curl_sys::curl_multi_remove_handle(
+                    self.multi.raw(),
+                    self.easy,
+                ));
+            }
+            self.easy = ptr::null_mut();
+        }
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        self.detach();
+    }
+}
+
+// Reads from the buffer of a reader, filling it first if needed.
+fn read<R: BufRead>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let data = reader.fill_buf()?;
+    let amt = cmp::min(data.len(), buf.len());
+    buf[..amt].copy_from_slice(&data[..amt]);
+    reader.consume(amt);
+    Ok(amt)
+}
+
+fn buffer<H>(easy: &mut Easy2<H>) -> &mut ReadBuffer {
+    easy.read_buffer()
+        .as_mut()
+        .expect("reading from a handle which isn't set up for it")
+}
+
+fn cvt(code: curl_sys::CURLMcode) -> Result<(), MultiError> {
+    if code == curl_sys::CURLM_OK {
+        Ok(())
+    } else {
+        Err(MultiError::new(code))
+    }
+}
+
+// Errors from the multi handle are reported as failures of the transfer,
+// carrying their description along.
+fn multi_error(e: MultiError) -> Error {
+    let mut err = Error::new(curl_sys::CURLE_FAILED_INIT);
+    err.set_extra(e.to_string());
+    err
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+use libc::c_void;
+
+use crate::ShareError;
+
+/// A shared data handle, used to share data between multiple easy handles.
+///
+/// A `Share` can be attached to any number of `Easy` or `Easy2` handles via
+/// the `share` method, after which those handles will share the pieces of
+/// state enabled on this share (for example the DNS cache or cookies). This
+/// corresponds to the `CURLSH` type and the `curl_share_*` family of
+/// functions in libcurl.
+///
+/// Shared state is guarded by mutexes managed by this type, so handles
+/// attached to the same share can be used concurrently from different
+/// threads. A `Share` is cheaply cloneable, and each clone refers to the same
+/// underlying share handle, which stays alive as long as any clone or any
+/// attached easy handle does.
+///
+/// # Examples
+///
+/// ```
+/// use curl::easy::{Easy, Share};
+///
+/// let mut share = Share::new();
+/// share.dns(true).unwrap();
+/// share.cookie(true).unwrap();
+///
+/// let mut a = Easy::new();
+/// a.share(&share).unwrap();
+/// let mut b = Easy::new();
+/// b.share(&share).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Share {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    raw: *mut curl_sys::CURLSH,
+    locks: Vec<Lock>,
+    // Whether the connection pool is shared, which libcurl only lets change
+    // while no handle is attached.
+    connect: AtomicBool,
+}
+
+// One lock for each `curl_lock_data` value libcurl may ask us to take. The
+// lock function and unlock function are separate callbacks, so rather than
+// holding a `MutexGuard` across them we track ownership with a flag.
+struct Lock {
+    locked: Mutex<bool>,
+    cvar: Condvar,
+}
+
+// Number of lock slots to allocate, one past the largest `curl_lock_data`
+// value libcurl currently knows about.
+const LOCK_SLOTS: usize = 8;
+
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+pub fn raw(share: &Share) -> *mut curl_sys::CURLSH {
+    share.inner.raw
+}
+
+pub fn shares_connections(share: &Share) -> bool {
+    share.inner.connect.load(Ordering::SeqCst)
+}
+
+impl Share {
+    /// Creates a new share handle with no data shared.
+    pub fn new() -> Share {
+        crate::init();
+        unsafe {
+            let raw = curl_sys::curl_share_init();
+            assert!(!raw.is_null());
+            let inner = Arc::new(Inner {
+                raw,
+                locks: (0..LOCK_SLOTS).map(|_| Lock::new()).collect(),
+                connect: AtomicBool::new(false),
+            });
+            let data = &*inner as *const Inner as *mut c_void;
+            let cb: curl_sys::curl_lock_function = lock_cb;
+            cvt(curl_sys::curl_share_setopt(
+                raw,
+                curl_sys::CURLSHOPT_LOCKFUNC,
+                cb,
+            ))
+            .expect("failed to set lock function");
+            let cb: curl_sys::curl_unlock_function = unlock_cb;
+            cvt(curl_sys::curl_share_setopt(
+                raw,
+                curl_sys::CURLSHOPT_UNLOCKFUNC,
+                cb,
+            ))
+            .expect("failed to set unlock function");
+            cvt(curl_sys::curl_share_setopt(
+                raw,
+                curl_sys::CURLSHOPT_USERDATA,
+                data,
+            ))
+            .expect("failed to set lock data");
+            Share { inner }
+        }
+    }
+
+    /// Share the DNS cache between attached handles.
+    ///
+    /// Cached host name resolutions will be shared by all easy handles using
+    /// this share.
+    ///
+    /// Corresponds to `CURL_LOCK_DATA_DNS`.
+    pub fn dns(&mut self, enable: bool) -> Result<(), ShareError> {
+        self.set(curl_sys::CURL_LOCK_DATA_DNS, enable)
+    }
+
+    /// Share cookie data between attached handles.
+    ///
+    /// Note that this does not activate the cookie engine of an easy handle
+    /// by itself, which still needs to be done with `cookie_file` or similar.
+    ///
+    /// Corresponds to `CURL_LOCK_DATA_COOKIE`.
+    pub fn cookie(&mut self, enable: bool) -> Result<(), ShareError> {
+        self.set(curl_sys::CURL_LOCK_DATA_COOKIE, enable)
+    }
+
+    /// Share the TLS session ID cache between attached handles.
+    ///
+    /// This reduces the time spent in the TLS handshake when reconnecting to
+    /// the same server.
+    ///
+    /// Corresponds to `CURL_LOCK_DATA_SSL_SESSION`.
+    pub fn ssl_session(&mut self, enable: bool) -> Result<(), ShareError> {
+        self.set(curl_sys::CURL_LOCK_DATA_SSL_SESSION, enable)
+    }
+
+    /// Share the connection pool between attached handles.
+    ///
+    /// This allows a connection opened by one easy handle to be reused by
+    /// another. Sockets of shared connections are closed without calling
+    /// `Handler::close_socket`.
+    ///
+    /// Corresponds to `CURL_LOCK_DATA_CONNECT`.
+    pub fn connect(&mut self, enable: bool) -> Result<(), ShareError> {
+        self.set(curl_sys::CURL_LOCK_DATA_CONNECT, enable)?;
+        self.inner.connect.store(enable, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Share the Public Suffix List between attached handles.
+    ///
+    /// Corresponds to `CURL_LOCK_DATA_PSL`.
+    pub fn psl(&mut self, enable: bool) -> Result<(), ShareError> {
+        self.set(curl_sys::CURL_LOCK_DATA_PSL, enable)
+    }
+
+    fn set(&mut self, data: curl_sys::curl_lock_data, enable: bool) -> Result<(), ShareError> {
+        let opt = if enable {
+            curl_sys::CURLSHOPT_SHARE
+        } else {
+            curl_sys::CURLSHOPT_UNSHARE
+        };
+        unsafe { cvt(curl_sys::curl_share_setopt(self.inner.raw, opt, data)) }
+    }
+}
+
+impl Default for Share {
+    fn default() -> Share {
+        Share::new()
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("raw", &self.inner.raw)
+            .finish()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        unsafe {
+            curl_sys::curl_share_cleanup(self.raw);
+        }
+    }
+}
+
+impl Lock {
+    fn new() -> Lock {
+        Lock {
+            locked: Mutex::new(false),
+            cvar: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) {
+        let mut locked = self.locked.lock().unwrap_or_else(|e| e.into_inner());
+        while *locked {
+            locked = self.cvar.wait(locked).unwrap_or_else(|e| e.into_inner());
+        }
+        *locked = true;
+    }
+
+    fn unlock(&self) {
+        let mut locked = self.locked.lock().unwrap_or_else(|e| e.into_inner());
+        *locked = false;
+        drop(locked);
+        self.cvar.notify_one();
+    }
+}
+
+impl Inner {
+    fn slot(&self, data: curl_sys::curl_lock_data) -> &Lock {
+        // Any data we don't know about is serialized through the first slot,
+        // which libcurl never uses itself.
+        self.locks.get(data as usize).unwrap_or(&self.locks[0])
+    }
+}
+
+fn cvt(code: curl_sys::CURLSHcode) -> Result<(), ShareError> {
+    if code == curl_sys::CURLSHE_OK {
+        Ok(())
+    } else {
+        Err(ShareError::new(code))
+    }
+}
+
+extern "C" fn lock_cb(
+    _handle: *mut curl_sys::CURL,
+    data: curl_sys::curl_lock_data,
+    _access: curl_sys::curl_lock_access,
+    userptr: *mut c_void,
+) {
+    unsafe {
+        (*(userptr as *const Inner)).slot(data).lock();
+    }
+}
+
+extern "C" fn unlock_cb(
+    _handle: *mut curl_sys::CURL,
+    data: curl_sys::curl_lock_data,
+    userptr: *mut c_void,
+) {
+    unsafe {
+        (*(userptr as *const Inner)).slot(data).unlock();
+    }
+}
@@ -0,0 +1,338 @@
+use std::fmt;
+use std::ptr;
+
+use libc::{c_int, c_long, c_uint, c_void};
+
+use crate::easy::{Easy2, Handler};
+use crate::Error;
+
+/// A WebSocket connection driven directly by the application.
+///
+/// This wraps an `Easy2` handle which is connected with `CONNECT_ONLY` set
+/// to 2, meaning libcurl performs the HTTP upgrade handshake and then hands
+/// the connection over to `send` and `recv`. This corresponds to the
+/// `curl_ws_send` and `curl_ws_recv` functions in libcurl.
+///
+/// Like `Easy2::send` and `Easy2::recv` the underlying socket is
+/// non-blocking, so both methods may fail with an error for which
+/// `is_again` returns `true`. Applications should then wait for the socket
+/// to become readable or writable before trying again.
+///
+/// WebSockets can also be used in a callback-driven fashion by performing a
+/// transfer on a `ws://` or `wss://` URL without `connect_only`, in which
+/// case incoming frames are delivered to `Handler::ws_write`.
+///
+/// Requires libcurl 7.86.0 or later and the `ws_7_86_0` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use curl::easy::{Easy2, Handler, WebSocket};
+///
+/// struct Nothing;
+/// impl Handler for Nothing {}
+///
+/// let mut easy = Easy2::new(Nothing);
+/// easy.url("ws://example.com/chat").unwrap();
+/// let mut ws = WebSocket::connect(easy).unwrap();
+/// ws.send_text("hello").unwrap();
+///
+/// let mut buf = [0; 1024];
+/// let (n, frame) = ws.recv(&mut buf).unwrap();
+/// if frame.is_text() {
+///     println!("{}", String::from_utf8_lossy(&buf[..n]));
+/// }
+/// ```
+pub struct WebSocket<H> {
+    easy: Easy2<H>,
+}
+
+/// Metadata about a received WebSocket frame.
+///
+/// This corresponds to the `curl_ws_frame` struct in libcurl. A frame whose
+/// payload doesn't fit in one buffer is delivered in several pieces, and
+/// `offset` and `bytes_left` describe where in the frame each piece falls.
+#[derive(Clone, Copy)]
+pub struct WsFrame {
+    flags: c_int,
+    offset: i64,
+    bytes_left: i64,
+    len: usize,
+}
+
+/// Flags describing a WebSocket frame to send with `WebSocket::send`.
+#[derive(Clone, Default)]
+pub struct WsFlags {
+    bits: c_uint,
+}
+
+impl<H: Handler> WebSocket<H> {
+    /// Connects to the WebSocket server configured on `easy`.
+    ///
+    /// The handle should have its URL set to a `ws://` or `wss://` URL. This
+    /// sets `CURLOPT_CONNECT_ONLY` to 2 and performs the upgrade handshake,
+    /// returning once the connection is ready to send and receive frames.
+    pub fn connect(easy: Easy2<H>) -> Result<WebSocket<H>, Error> {
+        let ws = WebSocket { easy };
+        unsafe {
+            ws.cvt(curl_sys::curl_easy_setopt(
+                ws.easy.raw(),
+                curl_sys::CURLOPT_CONNECT_ONLY,
+                2 as c_long,
+            ))?;
+        }
+        ws.easy.perform()?;
+        Ok(ws)
+    }
+}
+
+impl<H> WebSocket<H> {
+    /// Sends a frame, or part of one, over this connection.
+    ///
+    /// Returns the number of payload bytes sent, which may be less than the
+    /// length of `data`. The kind of frame is described by `flags`.
+    pub fn send(&mut self, data: &[u8], flags: &WsFlags) -> Result<usize, Error> {
+        let mut n = 0;
+        unsafe {
+            self.cvt(curl_sys::curl_ws_send(
+                self.easy.raw(),
+                data.as_ptr() as *const c_void,
+                data.len(),
+                &mut n,
+                0,
+                flags.bits,
+            ))?;
+        }
+        Ok(n)
+    }
+
+    /// Sends a text frame.
+    pub fn send_text(&mut self, text: &str) -> Result<usize, Error> {
+        self.send(text.as_bytes(), WsFlags::new().text(true))
+    }
+
+    /// Sends a binary frame.
+    pub fn send_binary(&mut self, data: &[u8]) -> Result<usize, Error> {
+        self.send(data, WsFlags::new().binary(true))
+    }
+
+    /// Sends a ping frame with the given payload.
+    pub fn ping(&mut self, data: &[u8]) -> Result<usize, Error> {
+        self.send(data, WsFlags::new().ping(true))
+    }
+
+    /// Sends a pong frame with the given payload.
+    ///
+    /// Note that libcurl answers pings with pongs automatically unless told
+    /// otherwise, so this is usually only useful for unsolicited pongs.
+    pub fn pong(&mut self, data: &[u8]) -> Result<usize, Error> {
+        self.send(data, WsFlags::new().pong(true))
+    }
+
+    /// Sends a close frame with the given status code and reason.
+    pub fn close(&mut self, code: u16, reason: &str) -> Result<usize, Error> {
+        let mut data = Vec::with_capacity(2 + reason.len());
+        data.extend_from_slice(&code.to_be_bytes());
+        data.extend_from_slice(reason.as_bytes());
+        self.send(&data, WsFlags::new().close(true))
+    }
+
+    /// Receives a frame, or part of one, from this connection.
+    ///
+    /// Returns the number of bytes written into `buf` along with the
+    /// metadata of the frame they belong to.
+    pub fn recv(&mut self, buf: &mut [u8]) -> Result<(usize, WsFrame), Error> {
+        let mut n = 0;
+        let mut meta = ptr::null();
+        unsafe {
+            self.cvt(curl_sys::curl_ws_recv(
+                self.easy.raw(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                &mut n,
+                &mut meta,
+            ))?;
+            Ok((n, WsFrame::from_raw(&*meta)))
+        }
+    }
+
+    /// Returns a reference to the underlying easy handle.
+    pub fn get_ref(&self) -> &Easy2<H> {
+        &self.easy
+    }
+
+    /// Returns a mutable reference to the underlying easy handle.
+    pub fn get_mut(&mut self) -> &mut Easy2<H> {
+        &mut self.easy
+    }
+
+    /// Consumes this connection, returning the underlying easy handle.
+    pub fn into_inner(self) -> Easy2<H> {
+        self.easy
+    }
+
+    fn cvt(&self, rc: curl_sys::CURLcode) -> Result<(), Error> {
+        if rc == curl_sys::CURLE_OK {
+            return Ok(());
+        }
+        let mut err = Error::new(rc);
+        if let Some(msg) = self.easy.take_error_buf() {
+            err.set_extra(msg);
+        }
+        Err(err)
+    }
+}
+
+impl<H: fmt::Debug> fmt::Debug for WebSocket<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("easy", &self.easy)
+            .finish()
+    }
+}
+
+impl WsFrame {
+    pub(crate) fn from_raw(raw: &curl_sys::curl_ws_frame) -> WsFrame {
+        WsFrame {
+            flags: raw.flags,
+            offset: raw.offset,
+            bytes_left: raw.bytesleft,
+            len: raw.len,
+        }
+    }
+
+    /// Returns whether this is a text frame.
+    pub fn is_text(&self) -> bool {
+        self.flag(curl_sys::CURLWS_TEXT)
+    }
+
+    /// Returns whether this is a binary frame.
+    pub fn is_binary(&self) -> bool {
+        self.flag(curl_sys::CURLWS_BINARY)
+    }
+
+    /// Returns whether this frame is a fragment of a message which is
+    /// continued in the following frames.
+    pub fn is_continued(&self) -> bool {
+        self.flag(curl_sys::CURLWS_CONT)
+    }
+
+    /// Returns whether this is a close frame.
+    pub fn is_close(&self) -> bool {
+        self.flag(curl_sys::CURLWS_CLOSE)
+    }
+
+    /// Returns whether this is a ping frame.
+    pub fn is_ping(&self) -> bool {
+        self.flag(curl_sys::CURLWS_PING)
+    }
+
+    /// Returns whether this is a pong frame.
+    pub fn is_pong(&self) -> bool {
+        self.flag(curl_sys::CURLWS_PONG)
+    }
+
+    /// Returns the offset of the data delivered alongside this metadata
+    /// within the payload of the frame.
+    pub fn offset(&self) -> u64 {
+        self.offset as u64
+    }
+
+    /// Returns the number of payload bytes of this frame that are yet to be
+    /// delivered.
+    pub fn bytes_left(&self) -> u64 {
+        self.bytes_left as u64
+    }
+
+    /// Returns the number of payload bytes delivered alongside this
+    /// metadata.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no payload bytes were delivered alongside this
+    /// metadata.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn flag(&self, bit: c_uint) -> bool {
+        self.flags as c_uint & bit != 0
+    }
+}
+
+impl fmt::Debug for WsFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WsFrame")
+            .field("text", &self.is_text())
+            .field("binary", &self.is_binary())
+            .field("continued", &self.is_continued())
+            .field("close", &self.is_close())
+            .field("ping", &self.is_ping())
+            .field("pong", &self.is_pong())
+            .field("offset", &self.offset)
+            .field("bytes_left", &self.bytes_left)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl WsFlags {
+    /// Creates a new set of flags with nothing set.
+    pub fn new() -> WsFlags {
+        WsFlags { bits: 0 }
+    }
+
+    /// The frame contains text data.
+    pub fn text(&mut self, on: bool) -> &mut WsFlags {
+        self.flag(curl_sys::CURLWS_TEXT, on)
+    }
+
+    /// The frame contains binary data.
+    pub fn binary(&mut self, on: bool) -> &mut WsFlags {
+        self.flag(curl_sys::CURLWS_BINARY, on)
+    }
+
+    /// The frame is a fragment of a message which is continued in the
+    /// following frames. The final fragment is sent without this flag.
+    pub fn continued(&mut self, on: bool) -> &mut WsFlags {
+        self.flag(curl_sys::CURLWS_CONT, on)
+    }
+
+    /// The frame is a close frame.
+    pub fn close(&mut self, on: bool) -> &mut WsFlags {
+        self.flag(curl_sys::CURLWS_CLOSE, on)
+    }
+
+    /// The frame is a ping frame.
+    pub fn ping(&mut self, on: bool) -> &mut WsFlags {
+        self.flag(curl_sys::CURLWS_PING, on)
+    }
+
+    /// The frame is a pong frame.
+    pub fn pong(&mut self, on: bool) -> &mut WsFlags {
+        self.flag(curl_sys::CURLWS_PONG, on)
+    }
+
+    fn flag(&mut self, bit: c_uint, on: bool) -> &mut WsFlags {
+        if on {
+            self.bits |= bit;
+        } else {
+            self.bits &= !bit;
+        }
+        self
+    }
+}
+
+impl fmt::Debug for WsFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WsFlags")
+            .field("text", &(self.bits & curl_sys::CURLWS_TEXT != 0))
+            .field("binary", &(self.bits & curl_sys::CURLWS_BINARY != 0))
+            .field("continued", &(self.bits & curl_sys::CURLWS_CONT != 0))
+            .field("close", &(self.bits & curl_sys::CURLWS_CLOSE != 0))
+            .field("ping", &(self.bits & curl_sys::CURLWS_PING != 0))
+            .field("pong", &(self.bits & curl_sys::CURLWS_PONG != 0))
+            .finish()
+    }
+}
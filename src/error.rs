@@ -409,6 +409,329 @@ impl fmt::Debug for ShareError {
 
 impl error::Error for ShareError {}
 
+/// An error from URL parsing and manipulation through `Url`.
+///
+/// This structure wraps a `CURLUcode`.
+#[cfg(feature = "url_7_62_0")]
+#[derive(Clone, PartialEq)]
+pub struct UrlError {
+    code: curl_sys::CURLUcode,
+}
+
+#[cfg(feature = "url_7_62_0")]
+impl UrlError {
+    /// Creates a new error from the underlying code returned by libcurl.
+    pub fn new(code: curl_sys::CURLUcode) -> UrlError {
+        UrlError { code }
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_HANDLE.
+    pub fn is_bad_handle(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_HANDLE
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_PARTPOINTER.
+    pub fn is_bad_partpointer(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_PARTPOINTER
+    }
+
+    /// Returns whether this error corresponds to CURLUE_MALFORMED_INPUT.
+    pub fn is_malformed_input(&self) -> bool {
+        self.code == curl_sys::CURLUE_MALFORMED_INPUT
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_PORT_NUMBER.
+    pub fn is_bad_port_number(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_PORT_NUMBER
+    }
+
+    /// Returns whether this error corresponds to CURLUE_UNSUPPORTED_SCHEME.
+    pub fn is_unsupported_scheme(&self) -> bool {
+        self.code == curl_sys::CURLUE_UNSUPPORTED_SCHEME
+    }
+
+    /// Returns whether this error corresponds to CURLUE_URLDECODE.
+    pub fn is_urldecode(&self) -> bool {
+        self.code == curl_sys::CURLUE_URLDECODE
+    }
+
+    /// Returns whether this error corresponds to CURLUE_OUT_OF_MEMORY.
+    pub fn is_out_of_memory(&self) -> bool {
+        self.code == curl_sys::CURLUE_OUT_OF_MEMORY
+    }
+
+    /// Returns whether this error corresponds to CURLUE_USER_NOT_ALLOWED.
+    pub fn is_user_not_allowed(&self) -> bool {
+        self.code == curl_sys::CURLUE_USER_NOT_ALLOWED
+    }
+
+    /// Returns whether this error corresponds to CURLUE_UNKNOWN_PART.
+    pub fn is_unknown_part(&self) -> bool {
+        self.code == curl_sys::CURLUE_UNKNOWN_PART
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_SCHEME.
+    pub fn is_no_scheme(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_SCHEME
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_USER.
+    pub fn is_no_user(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_USER
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_PASSWORD.
+    pub fn is_no_password(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_PASSWORD
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_OPTIONS.
+    pub fn is_no_options(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_OPTIONS
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_HOST.
+    pub fn is_no_host(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_HOST
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_PORT.
+    pub fn is_no_port(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_PORT
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_QUERY.
+    pub fn is_no_query(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_QUERY
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_FRAGMENT.
+    pub fn is_no_fragment(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_FRAGMENT
+    }
+
+    /// Returns whether this error corresponds to CURLUE_NO_ZONEID.
+    pub fn is_no_zoneid(&self) -> bool {
+        self.code == curl_sys::CURLUE_NO_ZONEID
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_FILE_URL.
+    pub fn is_bad_file_url(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_FILE_URL
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_FRAGMENT.
+    pub fn is_bad_fragment(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_FRAGMENT
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_HOSTNAME.
+    pub fn is_bad_hostname(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_HOSTNAME
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_IPV6.
+    pub fn is_bad_ipv6(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_IPV6
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_LOGIN.
+    pub fn is_bad_login(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_LOGIN
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_PASSWORD.
+    pub fn is_bad_password(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_PASSWORD
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_PATH.
+    pub fn is_bad_path(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_PATH
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_QUERY.
+    pub fn is_bad_query(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_QUERY
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_SCHEME.
+    pub fn is_bad_scheme(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_SCHEME
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_SLASHES.
+    pub fn is_bad_slashes(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_SLASHES
+    }
+
+    /// Returns whether this error corresponds to CURLUE_BAD_USER.
+    pub fn is_bad_user(&self) -> bool {
+        self.code == curl_sys::CURLUE_BAD_USER
+    }
+
+    /// Returns the value of the underlying error corresponding to libcurl.
+    pub fn code(&self) -> curl_sys::CURLUcode {
+        self.code
+    }
+
+    /// Returns curl's human-readable description of this error.
+    pub fn description(&self) -> &str {
+        // `curl_url_strerror` only exists since 7.80.0, so these are the
+        // messages it returns.
+        match self.code {
+            curl_sys::CURLUE_OK => "No error",
+            curl_sys::CURLUE_BAD_HANDLE => "An invalid CURLU pointer was passed as argument",
+            curl_sys::CURLUE_BAD_PARTPOINTER => "An invalid 'part' argument was passed as argument",
+            curl_sys::CURLUE_MALFORMED_INPUT => "Malformed input to a URL function",
+            curl_sys::CURLUE_BAD_PORT_NUMBER => {
+                "Port number was not a decimal number between 0 and 65535"
+            }
+            curl_sys::CURLUE_UNSUPPORTED_SCHEME => "Unsupported URL scheme",
+            curl_sys::CURLUE_URLDECODE => {
+                "URL decode error, most likely because of rubbish in the input"
+            }
+            curl_sys::CURLUE_OUT_OF_MEMORY => "A memory function failed",
+            curl_sys::CURLUE_USER_NOT_ALLOWED => {
+                "Credentials was passed in the URL when prohibited"
+            }
+            curl_sys::CURLUE_UNKNOWN_PART => "An unknown part ID was passed to a URL API function",
+            curl_sys::CURLUE_NO_SCHEME => "No scheme present",
+            curl_sys::CURLUE_NO_USER => "No user present",
+            curl_sys::CURLUE_NO_PASSWORD => "No password present",
+            curl_sys::CURLUE_NO_OPTIONS => "No options present",
+            curl_sys::CURLUE_NO_HOST => "No host present",
+            curl_sys::CURLUE_NO_PORT => "No port number present",
+            curl_sys::CURLUE_NO_QUERY => "No query present",
+            curl_sys::CURLUE_NO_FRAGMENT => "No fragment present",
+            curl_sys::CURLUE_NO_ZONEID => "No zoneid present",
+            curl_sys::CURLUE_BAD_FILE_URL => "Bad file:// URL",
+            curl_sys::CURLUE_BAD_FRAGMENT => "Bad fragment",
+            curl_sys::CURLUE_BAD_HOSTNAME => "Bad hostname",
+            curl_sys::CURLUE_BAD_IPV6 => "Bad IPv6 address",
+            curl_sys::CURLUE_BAD_LOGIN => "Bad login",
+            curl_sys::CURLUE_BAD_PASSWORD => "Bad password",
+            curl_sys::CURLUE_BAD_PATH => "Bad path",
+            curl_sys::CURLUE_BAD_QUERY => "Bad query",
+            curl_sys::CURLUE_BAD_SCHEME => "Bad scheme",
+            curl_sys::CURLUE_BAD_SLASHES => "Unsupported number of slashes following scheme",
+            curl_sys::CURLUE_BAD_USER => "Bad user",
+            _ => "CURLUcode unknown",
+        }
+    }
+}
+
+#[cfg(feature = "url_7_62_0")]
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+#[cfg(feature = "url_7_62_0")]
+impl fmt::Debug for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UrlError")
+            .field("description", &self.description())
+            .field("code", &self.code)
+            .finish()
+    }
+}
+
+#[cfg(feature = "url_7_62_0")]
+impl error::Error for UrlError {}
+
+/// An error from reading response headers with `Easy2::header`.
+///
+/// This structure wraps a `CURLHcode`.
+#[cfg(feature = "header_7_83_0")]
+#[derive(Clone, PartialEq)]
+pub struct HeaderError {
+    code: curl_sys::CURLHcode,
+}
+
+#[cfg(feature = "header_7_83_0")]
+impl HeaderError {
+    /// Creates a new error from the underlying code returned by libcurl.
+    pub fn new(code: curl_sys::CURLHcode) -> HeaderError {
+        HeaderError { code }
+    }
+
+    /// Returns whether this error corresponds to CURLHE_BADINDEX.
+    pub fn is_bad_index(&self) -> bool {
+        self.code == curl_sys::CURLHE_BADINDEX
+    }
+
+    /// Returns whether this error corresponds to CURLHE_MISSING.
+    pub fn is_missing(&self) -> bool {
+        self.code == curl_sys::CURLHE_MISSING
+    }
+
+    /// Returns whether this error corresponds to CURLHE_NOHEADERS.
+    pub fn is_no_headers(&self) -> bool {
+        self.code == curl_sys::CURLHE_NOHEADERS
+    }
+
+    /// Returns whether this error corresponds to CURLHE_NOREQUEST.
+    pub fn is_no_request(&self) -> bool {
+        self.code == curl_sys::CURLHE_NOREQUEST
+    }
+
+    /// Returns whether this error corresponds to CURLHE_OUT_OF_MEMORY.
+    pub fn is_out_of_memory(&self) -> bool {
+        self.code == curl_sys::CURLHE_OUT_OF_MEMORY
+    }
+
+    /// Returns whether this error corresponds to CURLHE_BAD_ARGUMENT.
+    pub fn is_bad_argument(&self) -> bool {
+        self.code == curl_sys::CURLHE_BAD_ARGUMENT
+    }
+
+    /// Returns whether this error corresponds to CURLHE_NOT_BUILT_IN.
+    pub fn is_not_built_in(&self) -> bool {
+        self.code == curl_sys::CURLHE_NOT_BUILT_IN
+    }
+
+    /// Returns the value of the underlying error corresponding to libcurl.
+    pub fn code(&self) -> curl_sys::CURLHcode {
+        self.code
+    }
+
+    /// Returns a human-readable description of this error code.
+    pub fn description(&self) -> &str {
+        match self.code {
+            curl_sys::CURLHE_BADINDEX => "header exists but not with this index",
+            curl_sys::CURLHE_MISSING => "no such header exists",
+            curl_sys::CURLHE_NOHEADERS => "no headers at all exist (yet)",
+            curl_sys::CURLHE_NOREQUEST => "no request with this number was used",
+            curl_sys::CURLHE_OUT_OF_MEMORY => "out of memory while processing",
+            curl_sys::CURLHE_BAD_ARGUMENT => "a function argument was not okay",
+            curl_sys::CURLHE_NOT_BUILT_IN => "the header API was disabled in the build",
+            _ => "unknown header error",
+        }
+    }
+}
+
+#[cfg(feature = "header_7_83_0")]
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+#[cfg(feature = "header_7_83_0")]
+impl fmt::Debug for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HeaderError")
+            .field("description", &self.description())
+            .field("code", &self.code)
+            .finish()
+    }
+}
+
+#[cfg(feature = "header_7_83_0")]
+impl error::Error for HeaderError {}
+
 /// An error from "multi" operations.
 ///
 /// THis structure wraps a `CURLMcode`.
@@ -604,6 +927,20 @@ impl From<ShareError> for io::Error {
     }
 }
 
+#[cfg(feature = "url_7_62_0")]
+impl From<UrlError> for io::Error {
+    fn from(e: UrlError) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, e)
+    }
+}
+
+#[cfg(feature = "header_7_83_0")]
+impl From<HeaderError> for io::Error {
+    fn from(e: HeaderError) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, e)
+    }
+}
+
 impl From<MultiError> for io::Error {
     fn from(e: MultiError) -> io::Error {
         io::Error::new(io::ErrorKind::Other, e)
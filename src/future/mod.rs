@@ -0,0 +1,365 @@
+//! Driving transfers from an async runtime.
+//!
+//! This module contains `Perform`, a future which runs an `Easy2` transfer on
+//! a private `Multi` handle. Rather than blocking in `Easy2::perform` the
+//! future registers libcurl's sockets and timeouts with an async runtime
+//! through the `Runtime` trait, and completes with the easy handle once the
+//! transfer is done.
+//!
+//! The `Runtime` trait is small enough to be implemented for any reactor. An
+//! implementation for tokio is available as `Tokio` when the `tokio` feature
+//! is enabled.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # #[cfg(all(unix, feature = "tokio"))]
+//! # async fn run() -> Result<(), curl::Error> {
+//! use curl::easy::{Easy2, Handler, WriteError};
+//! use curl::future::{Perform, Tokio};
+//!
+//! struct Collector(Vec<u8>);
+//!
+//! impl Handler for Collector {
+//!     fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+//!         self.0.extend_from_slice(data);
+//!         Ok(data.len())
+//!     }
+//! }
+//!
+//! let mut easy = Easy2::new(Collector(Vec::new()));
+//! easy.url("https://www.rust-lang.org/")?;
+//! let easy = Perform::new(easy, Tokio).await?;
+//! println!("{}", String::from_utf8_lossy(&easy.get_ref().0));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::easy::{Easy2, Handler};
+use crate::multi::{Easy2Handle, Events, Multi, Socket};
+use crate::{Error, MultiError};
+
+#[cfg(all(unix, feature = "tokio"))]
+pub use self::tokio::{Tokio, TokioIo};
+
+#[cfg(all(unix, feature = "tokio"))]
+mod tokio;
+
+/// The parts of an async runtime needed to drive libcurl.
+///
+/// A runtime provides readiness notifications for libcurl's sockets and
+/// timers for its timeouts.
+pub trait Runtime {
+    /// A socket registered with the runtime's reactor.
+    ///
+    /// The socket itself is owned by libcurl, so dropping this must only
+    /// deregister it and not close it.
+    type Io: Io;
+
+    /// A future which completes after a timeout has elapsed.
+    type Sleep: Future<Output = ()>;
+
+    /// Registers a socket opened by libcurl with the reactor.
+    fn register(&self, socket: Socket) -> io::Result<Self::Io>;
+
+    /// Returns a future which completes after `duration` has elapsed.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+}
+
+/// Readiness of a socket registered with a `Runtime`.
+///
+/// libcurl does not necessarily read or write until a socket would block, so
+/// these should report whether the socket is currently ready rather than
+/// whether it has become ready since the last call.
+pub trait Io {
+    /// Polls for the socket to be readable.
+    fn poll_readable(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Polls for the socket to be writable.
+    fn poll_writable(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+/// A future performing a transfer, created with `Perform::new`.
+///
+/// The future completes with the easy handle once the transfer has finished,
+/// or with the error the transfer failed with. Dropping the future before
+/// then aborts the transfer.
+pub struct Perform<H, R: Runtime> {
+    // Safety: This *must* be before `multi` as it must be dropped first.
+    handle: Option<Easy2Handle<H>>,
+    multi: Multi,
+    runtime: R,
+    shared: Arc<Mutex<Shared>>,
+    sockets: HashMap<Socket, Watched<R::Io>>,
+    sleep: Option<Pin<Box<R::Sleep>>>,
+    timeout_now: bool,
+    error: Option<Error>,
+}
+
+struct Watched<I> {
+    io: I,
+    read: bool,
+    write: bool,
+}
+
+// State updated from the multi handle's callbacks, applied the next time the
+// future is polled.
+#[derive(Default)]
+struct Shared {
+    // Socket updates in the order libcurl made them, as a socket number may
+    // be removed and then reused by a new connection.
+    sockets: Vec<(Socket, Option<(bool, bool)>)>,
+    timer: Option<Option<Duration>>,
+}
+
+// Safety: Going through the fields of `Perform`:
+//
+// * `handle` and `multi` hold raw libcurl handles, which aren't `Send` only
+//   because of the pointers. libcurl allows its handles to move between
+//   threads as long as they aren't used concurrently, and both are only ever
+//   used through `&mut self` by whoever is polling this future. The handler
+//   `H` they own is required to be `Send`.
+// * The callbacks installed on `multi` only capture `shared`, and libcurl
+//   only calls them from within calls made on `multi`, so on the thread
+//   polling this future as well.
+// * `runtime`, the registered sockets and the sleep future are required to
+//   be `Send` through the bounds below.
+// * `shared` is an `Arc<Mutex<_>>` of plain data, `error` is plain data and
+//   `timeout_now` is a `bool`.
+unsafe impl<H: Send, R> Send for Perform<H, R>
+where
+    R: Runtime + Send,
+    R::Io: Send,
+    R::Sleep: Send,
+{
+}
+
+// `Perform` never hands out pinned references to its fields, and none of
+// them rely on their address staying the same:
+//
+// * `handle`, `multi` and `shared` only point to heap allocations owned by
+//   libcurl or by an `Arc`, which stay put when the future moves.
+// * The sleep future is the only one polled through a `Pin`, and it is
+//   boxed, so moving `Perform` doesn't move it.
+// * `runtime`, the registered sockets, `error` and `timeout_now` are only
+//   used through `&mut`, never pinned.
+impl<H, R: Runtime> Unpin for Perform<H, R> {}
+
+impl<H: Handler, R: Runtime> Perform<H, R> {
+    /// Creates a future which performs the transfer configured on `easy`,
+    /// driven by `runtime`.
+    pub fn new(easy: Easy2<H>, runtime: R) -> Perform<H, R> {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let mut multi = Multi::new();
+        let (handle, error) = match callbacks(&mut multi, &shared).and_then(|()| multi.add2(easy)) {
+            Ok(handle) => (Some(handle), None),
+            Err(e) => (None, Some(multi_error(e))),
+        };
+        Perform {
+            handle,
+            multi,
+            runtime,
+            shared,
+            sockets: HashMap::new(),
+            sleep: None,
+            timeout_now: false,
+            error,
+        }
+    }
+
+    // Applies any updates made by the multi handle's callbacks.
+    fn update(&mut self) -> Result<(), Error> {
+        let shared = mem::take(&mut *lock(&self.shared));
+        for (socket, interest) in shared.sockets {
+            match interest {
+                Some((read, write)) => {
+                    if !self.sockets.contains_key(&socket) {
+                        let io = self.runtime.register(socket).map_err(io_error)?;
+                        self.sockets.insert(
+                            socket,
+                            Watched {
+                                io,
+                                read: false,
+                                write: false,
+                            },
+                        );
+                    }
+                    let watched = self.sockets.get_mut(&socket).unwrap();
+                    watched.read = read;
+                    watched.write = write;
+                }
+                None => {
+                    self.sockets.remove(&socket);
+                }
+            }
+        }
+        match shared.timer {
+            Some(Some(timeout)) if timeout == Duration::from_millis(0) => {
+                self.sleep = None;
+                self.timeout_now = true;
+            }
+            Some(Some(timeout)) => {
+                self.sleep = Some(Box::pin(self.runtime.sleep(timeout)));
+                self.timeout_now = false;
+            }
+            Some(None) => {
+                self.sleep = None;
+                self.timeout_now = false;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    // Lets libcurl make progress on one event, returning whether there was
+    // anything to do.
+    fn step(&mut self, cx: &mut Context<'_>) -> Result<bool, Error> {
+        self.update()?;
+
+        let expired = match &mut self.sleep {
+            Some(sleep) => sleep.as_mut().poll(cx).is_ready(),
+            None => false,
+        };
+        if self.timeout_now || expired {
+            self.timeout_now = false;
+            self.sleep = None;
+            self.multi.timeout().map_err(multi_error)?;
+            return Ok(true);
+        }
+
+        for (socket, watched) in self.sockets.iter_mut() {
+            let mut events = Events::new();
+            let mut ready = false;
+            if watched.read {
+                match watched.io.poll_readable(cx) {
+                    Poll::Ready(Ok(())) => {
+                        events.input(true);
+                        ready = true;
+                    }
+                    Poll::Ready(Err(_)) => {
+                        events.error(true);
+                        ready = true;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+            if watched.write {
+                match watched.io.poll_writable(cx) {
+                    Poll::Ready(Ok(())) => {
+                        events.output(true);
+                        ready = true;
+                    }
+                    Poll::Ready(Err(_)) => {
+                        events.error(true);
+                        ready = true;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+            if ready {
+                let socket = *socket;
+                self.multi.action(socket, &events).map_err(multi_error)?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // Returns the result of the transfer, if it has finished.
+    fn result(&self) -> Option<Result<(), Error>> {
+        let handle = self.handle.as_ref()?;
+        let mut result = None;
+        self.multi.messages(|msg| {
+            if let Some(res) = msg.result_for2(handle) {
+                result = Some(res);
+            }
+        });
+        result
+    }
+}
+
+impl<H: Handler, R: Runtime> Future for Perform<H, R> {
+    type Output = Result<Easy2<H>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Err(e));
+        }
+        if this.handle.is_none() {
+            panic!("`Perform` polled after completion");
+        }
+        loop {
+            if let Some(res) = this.result() {
+                let handle = this.handle.take().unwrap();
+                let easy = this.multi.remove2(handle).map_err(multi_error)?;
+                return Poll::Ready(res.map(|()| easy));
+            }
+            match this.step(cx) {
+                Ok(true) => {}
+                Ok(false) => return Poll::Pending,
+                Err(e) => {
+                    // The transfer can't make progress anymore, so this
+                    // completes the future as well.
+                    this.handle = None;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl<H: fmt::Debug, R: Runtime> fmt::Debug for Perform<H, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Perform")
+            .field("handle", &self.handle)
+            .field("multi", &self.multi)
+            .finish()
+    }
+}
+
+// Records socket and timer updates from libcurl in `shared`.
+fn callbacks(multi: &mut Multi, shared: &Arc<Mutex<Shared>>) -> Result<(), MultiError> {
+    let sockets = shared.clone();
+    multi.socket_function(move |socket, events, _| {
+        let interest = if events.remove() {
+            None
+        } else {
+            Some((events.input(), events.output()))
+        };
+        lock(&sockets).sockets.push((socket, interest));
+    })?;
+    let timer = shared.clone();
+    multi.timer_function(move |timeout| {
+        lock(&timer).timer = Some(timeout);
+        true
+    })
+}
+
+fn lock(shared: &Mutex<Shared>) -> std::sync::MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Errors from the multi handle or the runtime are reported as failures of
+// the transfer, carrying their description along.
+fn multi_error(e: MultiError) -> Error {
+    let mut err = Error::new(curl_sys::CURLE_FAILED_INIT);
+    err.set_extra(e.to_string());
+    err
+}
+
+fn io_error(e: io::Error) -> Error {
+    let mut err = Error::new(curl_sys::CURLE_FAILED_INIT);
+    err.set_extra(e.to_string());
+    err
+}
@@ -0,0 +1,85 @@
+use std::io;
+use std::os::unix::prelude::*;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use ::tokio::io::unix::AsyncFd;
+use ::tokio::io::Interest;
+use ::tokio::time::Sleep;
+use libc::c_short;
+
+use super::{Io, Runtime};
+use crate::multi::Socket;
+
+/// A `Runtime` using the tokio reactor and timers.
+///
+/// A `Perform` future using this must be polled from within a tokio runtime
+/// with both the I/O and time drivers enabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tokio;
+
+/// A socket registered with tokio, see `Runtime::Io`.
+#[derive(Debug)]
+pub struct TokioIo {
+    fd: AsyncFd<Fd>,
+}
+
+// A socket which is owned, and closed, by libcurl.
+#[derive(Debug)]
+struct Fd(RawFd);
+
+impl AsRawFd for Fd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+impl Runtime for Tokio {
+    type Io = TokioIo;
+    type Sleep = Sleep;
+
+    fn register(&self, socket: Socket) -> io::Result<TokioIo> {
+        let fd = AsyncFd::with_interest(Fd(socket), Interest::READABLE | Interest::WRITABLE)?;
+        Ok(TokioIo { fd })
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        ::tokio::time::sleep(duration)
+    }
+}
+
+impl Io for TokioIo {
+    fn poll_readable(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_read_ready(cx))?;
+            if let Ok(res) = guard.try_io(|fd| check(fd.get_ref().0, libc::POLLIN)) {
+                return Poll::Ready(res);
+            }
+        }
+    }
+
+    fn poll_writable(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_write_ready(cx))?;
+            if let Ok(res) = guard.try_io(|fd| check(fd.get_ref().0, libc::POLLOUT)) {
+                return Poll::Ready(res);
+            }
+        }
+    }
+}
+
+// tokio reports readiness edge-triggered, but libcurl may leave data unread
+// on a socket, so check whether the socket is still ready before reporting
+// it. Returning `WouldBlock` clears tokio's readiness.
+fn check(fd: RawFd, events: c_short) -> io::Result<()> {
+    let mut pfd = libc::pollfd {
+        fd,
+        events,
+        revents: 0,
+    };
+    match unsafe { libc::poll(&mut pfd, 1, 0) } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => Err(io::ErrorKind::WouldBlock.into()),
+        _ => Ok(()),
+    }
+}
@@ -60,13 +60,24 @@ use std::ffi::CStr;
 use std::str;
 use std::sync::Once;
 
+#[cfg(feature = "header_7_83_0")]
+pub use crate::error::HeaderError;
+#[cfg(feature = "url_7_62_0")]
+pub use crate::error::UrlError;
 pub use crate::error::{Error, FormError, MultiError, ShareError};
 mod error;
 
+#[cfg(feature = "url_7_62_0")]
+pub use crate::url::{Url, UrlOpt, UrlPart};
+#[cfg(feature = "url_7_62_0")]
+mod url;
+
 pub use crate::version::{Protocols, Version};
 mod version;
 
 pub mod easy;
+#[cfg(feature = "async")]
+pub mod future;
 pub mod multi;
 mod panic;
 
@@ -1,9 +1,13 @@
 //! Multi - initiating multiple requests simultaneously
 
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::fmt;
 use std::marker;
+use std::mem;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
 use std::time::Duration;
 
 use libc::{c_char, c_int, c_long, c_short, c_void};
@@ -11,7 +15,7 @@ use libc::{c_char, c_int, c_long, c_short, c_void};
 #[cfg(unix)]
 use libc::{pollfd, POLLIN, POLLOUT, POLLPRI};
 
-use crate::easy::{Easy, Easy2, List};
+use crate::easy::{mime, Easy, Easy2, Handler, List, Mime, SocketCloser, TransferStats};
 use crate::panic;
 use crate::{Error, MultiError};
 
@@ -36,13 +40,28 @@ pub struct Multi {
 #[derive(Debug)]
 struct RawMulti {
     handle: *mut curl_sys::CURLM,
+    // The MIME bodies of the easy handles attached to this multi handle, to
+    // be able to tell whether libcurl may copy one for a pushed stream.
+    mimes: Mutex<HashMap<usize, *const Option<Mime>>>,
+    // The socket closers of handles which were removed, kept alive for the
+    // connections they left behind in the connection cache.
+    closers: Mutex<Vec<Arc<SocketCloser>>>,
 }
 
 struct MultiData {
+    // Safety: This *must* be before the callbacks as removing these handles
+    // may call them.
+    pushed: Vec<Box<dyn Any>>,
     socket: Box<dyn FnMut(Socket, SocketEvents, usize) + Send>,
     timer: Box<dyn FnMut(Option<Duration>) -> bool + Send>,
+    push: Box<PushFn>,
+    raw: Weak<RawMulti>,
 }
 
+// Accepts or refuses a pushed stream, returning the `Easy2Handle` for it when
+// accepted.
+type PushFn = dyn FnMut(PushRequest) -> Option<Box<dyn Any>> + Send;
+
 /// Message from the `messages` function of a multi handle.
 ///
 /// Currently only indicates whether a transfer is done.
@@ -51,6 +70,27 @@ pub struct Message<'multi> {
     _multi: &'multi Multi,
 }
 
+/// A stream pushed by an HTTP/2 server, passed to the `push_function`
+/// callback of a multi handle.
+///
+/// This gives access to the transfer the push is associated with and to the
+/// headers of the request the server is pushing a response for.
+pub struct PushRequest<'a> {
+    parent: *mut curl_sys::CURL,
+    easy: *mut curl_sys::CURL,
+    multi: Arc<RawMulti>,
+    num_headers: usize,
+    headers: *mut curl_sys::curl_pushheaders,
+    _marker: marker::PhantomData<&'a curl_sys::curl_pushheaders>,
+}
+
+/// An iterator over the headers of a `PushRequest`, returned from
+/// `PushRequest::headers`.
+pub struct PushHeaders<'a> {
+    request: &'a PushRequest<'a>,
+    next: usize,
+}
+
 /// Wrapper around an easy handle while it's owned by a multi handle.
 ///
 /// Once an easy handle has been added to a multi handle then it can no longer
@@ -82,6 +122,13 @@ pub struct Easy2Handle<H> {
 struct DetachGuard {
     multi: Arc<RawMulti>,
     easy: *mut curl_sys::CURL,
+    // The closer libcurl closes the sockets of the connections the handle
+    // creates through, which must outlive them.
+    sockets: Option<Arc<SocketCloser>>,
+    // For the handle of a pushed stream which isn't accepted yet, where the
+    // `Easy2` stores the raw handle. libcurl cleans up the handle itself if
+    // the push ends up refused.
+    pending: *mut *mut curl_sys::CURL,
 }
 
 /// Notification of the events that have happened on a socket.
@@ -130,12 +177,20 @@ impl Multi {
             crate::init();
             let ptr = curl_sys::curl_multi_init();
             assert!(!ptr.is_null());
+            let raw = Arc::new(RawMulti {
+                handle: ptr,
+                mimes: Mutex::new(HashMap::new()),
+                closers: Mutex::new(Vec::new()),
+            });
             Multi {
-                raw: Arc::new(RawMulti { handle: ptr }),
                 data: Box::new(MultiData {
+                    pushed: Vec::new(),
                     socket: Box::new(|_, _, _| ()),
                     timer: Box::new(|_| true),
+                    push: Box::new(|_| None),
+                    raw: Arc::downgrade(&raw),
                 }),
+                raw,
             }
         }
     }
@@ -297,6 +352,121 @@ impl Multi {
         }
     }
 
+    /// Set the callback deciding whether to accept streams pushed by HTTP/2
+    /// servers.
+    ///
+    /// Without this callback all pushed streams are refused. When a server
+    /// pushes a stream on a connection this multi handle is multiplexing
+    /// transfers over, the callback is called with a `PushRequest` describing
+    /// the request the server is pushing a response for.
+    ///
+    /// The push is accepted by returning the `Easy2Handle` created with
+    /// `PushRequest::accept`, whose handler gets the data of the pushed
+    /// response. libcurl then adds the handle to this multi handle, and its
+    /// completion is reported through `messages` like any other transfer.
+    /// As the handle can't leave the callback, it is kept by the multi
+    /// handle until it's retrieved with `take_pushed`. Returning `None`
+    /// refuses the push, as does panicking.
+    ///
+    /// Pushes on transfers with a MIME body streaming data from a reader,
+    /// see `MimePart::data_reader`, are always refused without calling the
+    /// callback, as libcurl may copy the body for the pushed stream.
+    ///
+    /// Note that server push requires HTTP/2 multiplexing to be enabled with
+    /// `pipelining`.
+    pub fn push_function<H, F>(&mut self, mut f: F) -> Result<(), MultiError>
+    where
+        H: Handler + 'static,
+        F: FnMut(PushRequest) -> Option<Easy2Handle<H>> + Send + 'static,
+    {
+        self._push_function(Box::new(move |request| {
+            let easy = request.easy;
+            let mut handle = f(request)?;
+            // Only the handle accepting this push may be returned.
+            if handle.raw() != easy || handle.guard.pending.is_null() {
+                return None;
+            }
+            handle.easy.bind();
+            handle
+                .guard
+                .attach(handle.easy.mime_ptr(), handle.easy.sockets());
+            Some(Box::new(handle) as Box<dyn Any>)
+        }))
+    }
+
+    fn _push_function(&mut self, f: Box<PushFn>) -> Result<(), MultiError> {
+        self.data.push = f;
+        let cb: curl_sys::curl_push_callback = cb;
+        self.setopt_ptr(
+            curl_sys::CURLMOPT_PUSHFUNCTION,
+            cb as usize as *const c_char,
+        )?;
+        let ptr = &*self.data as *const _;
+        self.setopt_ptr(curl_sys::CURLMOPT_PUSHDATA, ptr as *const c_char)?;
+        return Ok(());
+
+        extern "C" fn cb(
+            parent: *mut curl_sys::CURL,
+            easy: *mut curl_sys::CURL,
+            num_headers: usize,
+            headers: *mut curl_sys::curl_pushheaders,
+            userp: *mut c_void,
+        ) -> c_int {
+            let accepted = panic::catch(|| unsafe {
+                let data = &mut *(userp as *mut MultiData);
+                let multi = data.raw.upgrade()?;
+                // libcurl copies the MIME body of the parent into the handle
+                // for the push in some versions, which would then share the
+                // parent's readers.
+                if let Some(mime) = lock(&multi.mimes).get(&(parent as usize)) {
+                    if (**mime).as_ref().is_some_and(mime::has_readers) {
+                        return None;
+                    }
+                }
+                let request = PushRequest {
+                    parent,
+                    easy,
+                    multi,
+                    num_headers,
+                    headers,
+                    _marker: marker::PhantomData,
+                };
+                let handle = (data.push)(request)?;
+                data.pushed.push(handle);
+                Some(())
+            })
+            .flatten()
+            .is_some();
+            if accepted {
+                curl_sys::CURL_PUSH_OK
+            } else {
+                curl_sys::CURL_PUSH_DENY
+            }
+        }
+    }
+
+    /// Takes the handles of streams accepted by the `push_function` callback
+    /// so far.
+    ///
+    /// These are the handles returned from the callback, owned by this multi
+    /// handle just like ones returned from `add2`. They can be removed from
+    /// it with `remove2` once their transfer is done.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an accepted handle has a handler type other than `H`, which
+    /// can only happen if the callback was replaced with one for another
+    /// handler type before taking the handles accepted by the previous one.
+    pub fn take_pushed<H: 'static>(&mut self) -> Vec<Easy2Handle<H>> {
+        mem::take(&mut self.data.pushed)
+            .into_iter()
+            .map(|handle| match handle.downcast::<Easy2Handle<H>>() {
+                Ok(handle) => *handle,
+                Err(_) => panic!("pushed handle taken with the wrong handler type"),
+            })
+            .collect()
+    }
+
     /// Enable or disable HTTP pipelining and multiplexing.
     ///
     /// When http_1 is true, enable HTTP/1.1 pipelining, which means that if
@@ -418,26 +588,27 @@ impl Multi {
         unsafe {
             cvt(curl_sys::curl_multi_add_handle(self.raw.handle, easy.raw()))?;
         }
+        let mime = easy.inner_mut().mime_ptr();
+        let sockets = easy.sockets();
         Ok(EasyHandle {
-            guard: DetachGuard {
-                multi: self.raw.clone(),
-                easy: easy.raw(),
-            },
+            guard: DetachGuard::new(&self.raw, easy.raw(), mime, sockets),
             easy,
             _marker: marker::PhantomData,
         })
     }
 
     /// Same as `add`, but works with the `Easy2` type.
-    pub fn add2<H>(&self, easy: Easy2<H>) -> Result<Easy2Handle<H>, MultiError> {
+    pub fn add2<H>(&self, mut easy: Easy2<H>) -> Result<Easy2Handle<H>, MultiError> {
+        // This only fails if the entries from `Handler::resolve` can't be
+        // handed to libcurl.
+        if easy.start().is_err() {
+            return Err(MultiError::new(curl_sys::CURLM_BAD_EASY_HANDLE));
+        }
         unsafe {
             cvt(curl_sys::curl_multi_add_handle(self.raw.handle, easy.raw()))?;
         }
         Ok(Easy2Handle {
-            guard: DetachGuard {
-                multi: self.raw.clone(),
-                easy: easy.raw(),
-            },
+            guard: DetachGuard::new(&self.raw, easy.raw(), easy.mime_ptr(), easy.sockets()),
             easy,
             _marker: marker::PhantomData,
         })
@@ -456,12 +627,14 @@ impl Multi {
     /// All other easy handles and transfers will remain unaffected.
     pub fn remove(&self, mut easy: EasyHandle) -> Result<Easy, MultiError> {
         easy.guard.detach()?;
+        easy.easy.detach_sockets();
         Ok(easy.easy)
     }
 
     /// Same as `remove`, but for `Easy2Handle`.
     pub fn remove2<H>(&self, mut easy: Easy2Handle<H>) -> Result<Easy2<H>, MultiError> {
         easy.guard.detach()?;
+        easy.easy.detach_sockets();
         Ok(easy.easy)
     }
 
@@ -854,6 +1027,10 @@ fn cvt(code: curl_sys::CURLMcode) -> Result<(), MultiError> {
     }
 }
 
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 impl fmt::Debug for Multi {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Multi").field("raw", &self.raw).finish()
@@ -893,6 +1070,8 @@ macro_rules! impl_easy_getters {
                 local_ip -> Option<&str>,
                 local_port -> u16,
                 num_connects -> u64,
+                ftp_entry_path -> Option<&str>,
+                stats -> TransferStats,
             }
 
             get_mut {
@@ -1059,9 +1238,43 @@ impl<H: fmt::Debug> fmt::Debug for Easy2Handle<H> {
 }
 
 impl DetachGuard {
+    fn new(
+        multi: &Arc<RawMulti>,
+        easy: *mut curl_sys::CURL,
+        mime: *const Option<Mime>,
+        sockets: Arc<SocketCloser>,
+    ) -> DetachGuard {
+        let mut guard = DetachGuard {
+            multi: multi.clone(),
+            easy,
+            sockets: None,
+            pending: ptr::null_mut(),
+        };
+        guard.attach(mime, sockets);
+        guard
+    }
+
+    /// Records that the referenced easy handle is attached to the multi
+    /// handle, with `mime` pointing to its MIME body and `sockets` being the
+    /// closer of its sockets for as long as it is.
+    fn attach(&mut self, mime: *const Option<Mime>, sockets: Arc<SocketCloser>) {
+        self.pending = ptr::null_mut();
+        self.sockets = Some(sockets);
+        lock(&self.multi.mimes).insert(self.easy as usize, mime);
+    }
+
     /// Detach the referenced easy handle from its multi handle manually.
     /// Subsequent calls to this method will have no effect.
     fn detach(&mut self) -> Result<(), MultiError> {
+        if !self.pending.is_null() {
+            // The handle of a push which ends up refused, which libcurl
+            // cleans up itself, so the `Easy2` mustn't.
+            unsafe {
+                *self.pending = ptr::null_mut();
+            }
+            self.pending = ptr::null_mut();
+            self.easy = ptr::null_mut();
+        }
         if !self.easy.is_null() {
             unsafe {
                 cvt(curl_sys::curl_multi_remove_handle(
@@ -1069,6 +1282,18 @@ impl DetachGuard {
                     self.easy,
                 ))?
             }
+            lock(&self.multi.mimes).remove(&(self.easy as usize));
+
+            // libcurl may close the sockets the handle left behind in the
+            // connection cache at any later point, so keep their closer
+            // around until they're gone, dropping those which are done.
+            if let Some(sockets) = self.sockets.take() {
+                let mut closers = lock(&self.multi.closers);
+                closers.retain(|c| !c.is_idle());
+                if !sockets.is_idle() {
+                    closers.push(sockets);
+                }
+            }
 
             // Set easy to null to signify that the handle was removed.
             self.easy = ptr::null_mut();
@@ -1176,6 +1401,117 @@ impl<'a> fmt::Debug for Message<'a> {
     }
 }
 
+impl<'a> PushRequest<'a> {
+    /// Accepts the push, creating the handle for the pushed stream with
+    /// `handler` receiving its data.
+    ///
+    /// The handle must be returned from the `push_function` callback for the
+    /// push to be accepted, and its token can be set with `set_token` before
+    /// that to tell it apart in `messages`.
+    pub fn accept<H: Handler>(self, handler: H) -> Easy2Handle<H> {
+        let mut easy = unsafe { Easy2::from_duplicate(self.easy, handler) };
+        Easy2Handle {
+            guard: DetachGuard {
+                multi: self.multi,
+                easy: self.easy,
+                sockets: None,
+                pending: easy.handle_ptr(),
+            },
+            easy,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Returns the token associated with the transfer this stream was pushed
+    /// on.
+    ///
+    /// This function will return the token assigned with
+    /// `EasyHandle::set_token` to the parent transfer.
+    pub fn parent_token(&self) -> Result<usize, Error> {
+        unsafe {
+            let mut p = 0usize;
+            crate::cvt(curl_sys::curl_easy_getinfo(
+                self.parent,
+                curl_sys::CURLINFO_PRIVATE,
+                &mut p,
+            ))?;
+            Ok(p)
+        }
+    }
+
+    /// Get a pointer to the raw underlying CURL handle of the transfer this
+    /// stream was pushed on.
+    pub fn parent_raw(&self) -> *mut curl_sys::CURL {
+        self.parent
+    }
+
+    /// Returns the value of the pushed request header `name`, such as
+    /// `:path` or `:authority`.
+    ///
+    /// Returns `None` if there is no such header or its value isn't valid
+    /// UTF-8.
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        let name = CString::new(name).ok()?;
+        unsafe {
+            let value = curl_sys::curl_pushheader_byname(self.headers, name.as_ptr());
+            if value.is_null() {
+                None
+            } else {
+                CStr::from_ptr(value).to_str().ok()
+            }
+        }
+    }
+
+    /// Returns an iterator over the names and values of all the pushed
+    /// request headers.
+    pub fn headers(&self) -> PushHeaders<'_> {
+        PushHeaders {
+            request: self,
+            next: 0,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for PushRequest<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PushRequest")
+            .field("parent", &self.parent)
+            .field("num_headers", &self.num_headers)
+            .finish()
+    }
+}
+
+impl<'a> Iterator for PushHeaders<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<(&'a str, &'a str)> {
+        while self.next < self.request.num_headers {
+            let header = unsafe {
+                let ptr = curl_sys::curl_pushheader_bynum(self.request.headers, self.next);
+                self.next += 1;
+                if ptr.is_null() {
+                    continue;
+                }
+                CStr::from_ptr(ptr).to_str()
+            };
+            // Headers are stored as `name:value`, where pseudo header names
+            // start with a colon themselves.
+            if let Ok(header) = header {
+                if let Some(i) = header.get(1..).and_then(|h| h.find(':')) {
+                    return Some((&header[..i + 1], &header[i + 2..]));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a> fmt::Debug for PushHeaders<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PushHeaders").finish()
+    }
+}
+
 impl Events {
     /// Creates a new blank event bit mask.
     pub fn new() -> Events {
@@ -0,0 +1,452 @@
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ptr;
+use std::str::FromStr;
+
+use libc::{c_char, c_uint};
+
+use crate::UrlError;
+
+/// A parsed URL, backed by libcurl's own URL parser.
+///
+/// This corresponds to the `CURLU` handle in libcurl and the `curl_url_*`
+/// family of functions. Since the parsing is done by libcurl itself, a `Url`
+/// is normalized and interpreted exactly the same way it would be when
+/// passed to a transfer, and can be handed to an easy handle directly with
+/// the `curlu` method.
+///
+/// Requires libcurl 7.62.0 or later and the `url_7_62_0` feature.
+///
+/// # Examples
+///
+/// ```
+/// use curl::Url;
+///
+/// let mut url = Url::parse("https://example.com/a/b?x=1#frag").unwrap();
+/// assert_eq!(url.host().unwrap().as_deref(), Some("example.com"));
+/// assert_eq!(url.path().unwrap().as_deref(), Some("/a/b"));
+///
+/// url.set_port(Some(8443)).unwrap();
+/// url.append_query_pair("name", "a b").unwrap();
+/// assert_eq!(
+///     url.url().unwrap(),
+///     "https://example.com:8443/a/b?x=1&name=a+b#frag",
+/// );
+///
+/// let next = url.join("../c").unwrap();
+/// assert_eq!(next.url().unwrap(), "https://example.com:8443/c");
+/// ```
+pub struct Url {
+    raw: *mut curl_sys::CURLU,
+}
+
+/// The individual components of a `Url` that can be read or written.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlPart {
+    /// The full URL.
+    Url = curl_sys::CURLUPART_URL as isize,
+    /// The scheme, such as `https`.
+    Scheme = curl_sys::CURLUPART_SCHEME as isize,
+    /// The user name.
+    User = curl_sys::CURLUPART_USER as isize,
+    /// The password.
+    Password = curl_sys::CURLUPART_PASSWORD as isize,
+    /// The login options, a protocol specific part of the credentials.
+    Options = curl_sys::CURLUPART_OPTIONS as isize,
+    /// The host name or address.
+    Host = curl_sys::CURLUPART_HOST as isize,
+    /// The port number.
+    Port = curl_sys::CURLUPART_PORT as isize,
+    /// The path, including the leading slash.
+    Path = curl_sys::CURLUPART_PATH as isize,
+    /// The query, without the leading question mark.
+    Query = curl_sys::CURLUPART_QUERY as isize,
+    /// The fragment, without the leading hash sign.
+    Fragment = curl_sys::CURLUPART_FRAGMENT as isize,
+    /// The zone id of an IPv6 host address. (Added in 7.65.0)
+    ZoneId = curl_sys::CURLUPART_ZONEID as isize,
+}
+
+/// Structure which stores the flags to pass to `Url::get` and `Url::set`, as
+/// well as `Url::parse_with`.
+#[derive(Clone, Default)]
+pub struct UrlOpt {
+    bits: c_uint,
+}
+
+impl Url {
+    /// Creates a new, empty URL handle.
+    ///
+    /// All parts of the URL are unset, so they need to be filled in with the
+    /// various setters before the URL can be used.
+    pub fn new() -> Url {
+        crate::init();
+        unsafe {
+            let raw = curl_sys::curl_url();
+            assert!(!raw.is_null());
+            Url { raw }
+        }
+    }
+
+    /// Parses an absolute URL.
+    ///
+    /// The URL must contain a scheme, unless guessing is requested with
+    /// `parse_with`.
+    pub fn parse(url: &str) -> Result<Url, UrlError> {
+        Url::parse_with(url, &UrlOpt::new())
+    }
+
+    /// Parses an absolute URL with the given parsing flags.
+    pub fn parse_with(url: &str, opts: &UrlOpt) -> Result<Url, UrlError> {
+        let mut ret = Url::new();
+        ret.set(UrlPart::Url, Some(url), opts)?;
+        Ok(ret)
+    }
+
+    /// Resolves a possibly relative URL against this one, returning the
+    /// result as a new URL.
+    ///
+    /// This follows the same rules libcurl uses when following a redirect,
+    /// so it can be used to interpret the value of a `Location` header.
+    pub fn join(&self, url: &str) -> Result<Url, UrlError> {
+        let mut ret = self.clone();
+        ret.set(UrlPart::Url, Some(url), &UrlOpt::new())?;
+        Ok(ret)
+    }
+
+    /// Returns the full, normalized URL.
+    pub fn url(&self) -> Result<String, UrlError> {
+        unsafe { self.get_raw(UrlPart::Url, &UrlOpt::new()) }.map(|s| s.unwrap_or_default())
+    }
+
+    /// Returns the scheme of this URL, if any.
+    pub fn scheme(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Scheme, &UrlOpt::new())
+    }
+
+    /// Returns the user name of this URL, if any.
+    pub fn user(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::User, &UrlOpt::new())
+    }
+
+    /// Returns the password of this URL, if any.
+    pub fn password(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Password, &UrlOpt::new())
+    }
+
+    /// Returns the login options of this URL, if any.
+    pub fn options(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Options, &UrlOpt::new())
+    }
+
+    /// Returns the host of this URL, if any.
+    pub fn host(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Host, &UrlOpt::new())
+    }
+
+    /// Returns the port number explicitly set in this URL, if any.
+    ///
+    /// Use `get` with `UrlOpt::default_port` to fall back to the default
+    /// port of the scheme instead.
+    pub fn port(&self) -> Result<Option<u16>, UrlError> {
+        match self.get(UrlPart::Port, &UrlOpt::new())? {
+            Some(port) => match port.parse() {
+                Ok(port) => Ok(Some(port)),
+                Err(_) => Err(UrlError::new(curl_sys::CURLUE_BAD_PORT_NUMBER)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the path of this URL, if any.
+    pub fn path(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Path, &UrlOpt::new())
+    }
+
+    /// Returns the query of this URL, if any.
+    pub fn query(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Query, &UrlOpt::new())
+    }
+
+    /// Returns the fragment of this URL, if any.
+    pub fn fragment(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::Fragment, &UrlOpt::new())
+    }
+
+    /// Returns the IPv6 zone id of this URL, if any.
+    pub fn zoneid(&self) -> Result<Option<String>, UrlError> {
+        self.get(UrlPart::ZoneId, &UrlOpt::new())
+    }
+
+    /// Sets or clears the scheme of this URL.
+    pub fn set_scheme(&mut self, scheme: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Scheme, scheme, &UrlOpt::new())
+    }
+
+    /// Sets or clears the user name of this URL.
+    pub fn set_user(&mut self, user: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::User, user, &UrlOpt::new())
+    }
+
+    /// Sets or clears the password of this URL.
+    pub fn set_password(&mut self, password: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Password, password, &UrlOpt::new())
+    }
+
+    /// Sets or clears the login options of this URL.
+    pub fn set_options(&mut self, options: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Options, options, &UrlOpt::new())
+    }
+
+    /// Sets or clears the host of this URL.
+    pub fn set_host(&mut self, host: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Host, host, &UrlOpt::new())
+    }
+
+    /// Sets or clears the port number of this URL.
+    pub fn set_port(&mut self, port: Option<u16>) -> Result<(), UrlError> {
+        let port = port.map(|p| p.to_string());
+        self.set(UrlPart::Port, port.as_deref(), &UrlOpt::new())
+    }
+
+    /// Sets or clears the path of this URL.
+    pub fn set_path(&mut self, path: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Path, path, &UrlOpt::new())
+    }
+
+    /// Sets or clears the query of this URL.
+    ///
+    /// The query is used as-is, see `append_query_pair` for adding
+    /// individual encoded pairs.
+    pub fn set_query(&mut self, query: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Query, query, &UrlOpt::new())
+    }
+
+    /// Sets or clears the fragment of this URL.
+    pub fn set_fragment(&mut self, fragment: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::Fragment, fragment, &UrlOpt::new())
+    }
+
+    /// Sets or clears the IPv6 zone id of this URL.
+    pub fn set_zoneid(&mut self, zoneid: Option<&str>) -> Result<(), UrlError> {
+        self.set(UrlPart::ZoneId, zoneid, &UrlOpt::new())
+    }
+
+    /// Appends a `name=value` pair to the query of this URL.
+    ///
+    /// Both the name and the value are URL encoded by libcurl, and the pair
+    /// is separated from any existing query with an ampersand. Note that
+    /// libcurl leaves the first `=` of the pair unencoded, so `name` should
+    /// not contain one.
+    pub fn append_query_pair(&mut self, name: &str, value: &str) -> Result<(), UrlError> {
+        let pair = format!("{}={}", name, value);
+        let mut opts = UrlOpt::new();
+        opts.append_query(true).url_encode(true);
+        self.set(UrlPart::Query, Some(&pair), &opts)
+    }
+
+    /// Reads a part of this URL with the given flags.
+    ///
+    /// Returns `None` if the part is not present in the URL.
+    pub fn get(&self, part: UrlPart, opts: &UrlOpt) -> Result<Option<String>, UrlError> {
+        match unsafe { self.get_raw(part, opts) } {
+            Err(ref e) if part != UrlPart::Url && is_missing(e) => Ok(None),
+            other => other,
+        }
+    }
+
+    /// Sets or clears a part of this URL with the given flags.
+    ///
+    /// Setting `UrlPart::Url` to a relative URL resolves it against the
+    /// current contents of this handle.
+    pub fn set(
+        &mut self,
+        part: UrlPart,
+        value: Option<&str>,
+        opts: &UrlOpt,
+    ) -> Result<(), UrlError> {
+        let value = match value {
+            Some(s) => {
+                Some(CString::new(s).map_err(|_| UrlError::new(curl_sys::CURLUE_MALFORMED_INPUT))?)
+            }
+            None => None,
+        };
+        let ptr = value.as_ref().map(|s| s.as_ptr()).unwrap_or(ptr::null());
+        unsafe {
+            cvt(curl_sys::curl_url_set(
+                self.raw,
+                part as curl_sys::CURLUPart,
+                ptr,
+                opts.bits,
+            ))
+        }
+    }
+
+    unsafe fn get_raw(&self, part: UrlPart, opts: &UrlOpt) -> Result<Option<String>, UrlError> {
+        let mut p = ptr::null_mut::<c_char>();
+        cvt(curl_sys::curl_url_get(
+            self.raw,
+            part as curl_sys::CURLUPart,
+            &mut p,
+            opts.bits,
+        ))?;
+        if p.is_null() {
+            return Ok(None);
+        }
+        let ret = String::from_utf8_lossy(CStr::from_ptr(p).to_bytes()).into_owned();
+        curl_sys::curl_free(p as *mut _);
+        Ok(Some(ret))
+    }
+}
+
+pub fn raw(url: &Url) -> *mut curl_sys::CURLU {
+    url.raw
+}
+
+unsafe impl Send for Url {}
+
+impl Default for Url {
+    fn default() -> Url {
+        Url::new()
+    }
+}
+
+impl Clone for Url {
+    fn clone(&self) -> Url {
+        unsafe {
+            let raw = curl_sys::curl_url_dup(self.raw);
+            assert!(!raw.is_null());
+            Url { raw }
+        }
+    }
+}
+
+impl FromStr for Url {
+    type Err = UrlError;
+
+    fn from_str(s: &str) -> Result<Url, UrlError> {
+        Url::parse(s)
+    }
+}
+
+impl fmt::Debug for Url {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Url")
+            .field("url", &self.url().ok())
+            .finish()
+    }
+}
+
+impl Drop for Url {
+    fn drop(&mut self) {
+        unsafe { curl_sys::curl_url_cleanup(self.raw) }
+    }
+}
+
+impl UrlOpt {
+    /// Creates a new set of URL flags with nothing enabled.
+    pub fn new() -> UrlOpt {
+        UrlOpt { bits: 0 }
+    }
+
+    /// When reading the port, return the default port of the scheme if no
+    /// port was explicitly set.
+    pub fn default_port(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_DEFAULT_PORT, on)
+    }
+
+    /// When reading the port or full URL, omit the port if it matches the
+    /// default port of the scheme.
+    pub fn no_default_port(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_NO_DEFAULT_PORT, on)
+    }
+
+    /// When reading the scheme, return `https` if no scheme was set.
+    pub fn default_scheme(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_DEFAULT_SCHEME, on)
+    }
+
+    /// Accept schemes that the linked libcurl does not support.
+    pub fn non_support_scheme(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_NON_SUPPORT_SCHEME, on)
+    }
+
+    /// Leave `.` and `..` sequences in the path alone instead of squashing
+    /// them.
+    pub fn path_as_is(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_PATH_AS_IS, on)
+    }
+
+    /// Reject URLs that contain a user name or password.
+    pub fn disallow_user(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_DISALLOW_USER, on)
+    }
+
+    /// URL decode parts when reading them.
+    pub fn url_decode(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_URLDECODE, on)
+    }
+
+    /// URL encode parts when setting them.
+    pub fn url_encode(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_URLENCODE, on)
+    }
+
+    /// When setting the query, append to the existing query with an
+    /// ampersand instead of replacing it.
+    pub fn append_query(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_APPENDQUERY, on)
+    }
+
+    /// When parsing a URL without a scheme, guess the scheme from the host
+    /// name the same way the curl tool does.
+    pub fn guess_scheme(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_GUESS_SCHEME, on)
+    }
+
+    /// Allow an empty authority for unknown schemes. (Added in 7.67.0)
+    pub fn no_authority(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_NO_AUTHORITY, on)
+    }
+
+    /// Allow spaces in the URL when parsing. (Added in 7.78.0)
+    pub fn allow_space(&mut self, on: bool) -> &mut UrlOpt {
+        self.flag(curl_sys::CURLU_ALLOW_SPACE, on)
+    }
+
+    fn flag(&mut self, bit: c_uint, on: bool) -> &mut UrlOpt {
+        if on {
+            self.bits |= bit;
+        } else {
+            self.bits &= !bit;
+        }
+        self
+    }
+}
+
+impl fmt::Debug for UrlOpt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UrlOpt").field("bits", &self.bits).finish()
+    }
+}
+
+fn is_missing(e: &UrlError) -> bool {
+    e.is_no_scheme()
+        || e.is_no_user()
+        || e.is_no_password()
+        || e.is_no_options()
+        || e.is_no_host()
+        || e.is_no_port()
+        || e.is_no_query()
+        || e.is_no_fragment()
+        || e.is_no_zoneid()
+}
+
+fn cvt(code: curl_sys::CURLUcode) -> Result<(), UrlError> {
+    if code == curl_sys::CURLUE_OK {
+        Ok(())
+    } else {
+        Err(UrlError::new(code))
+    }
+}
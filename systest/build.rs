@@ -40,7 +40,7 @@ fn main() {
         }
     });
     cfg.type_name(|s, is_struct, _is_union| match s {
-        "CURL" | "CURLM" | "CURLSH" | "curl_version_info_data" => s.to_string(),
+        "CURL" | "CURLM" | "CURLSH" | "CURLU" | "curl_version_info_data" => s.to_string(),
         "curl_khtype" | "curl_khstat" | "curl_khmatch" => format!("enum {}", s),
         s if is_struct => format!("struct {}", s),
         "sockaddr" => "struct sockaddr".to_string(),
@@ -64,6 +64,24 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 86) {
+            match s {
+                "curl_ws_frame" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 83) {
+            match s {
+                "curl_header" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 74) {
+            match s {
+                "curl_hstsentry" | "curl_index" => return true,
+                _ => {}
+            }
+        }
 
         false
     });
@@ -72,14 +90,40 @@ fn main() {
     cfg.skip_const(move |s| {
         if version < (8, 19) {
             match s {
-                // This constant changed value.
+                // These constants changed value.
                 "CURLAUTH_ANY" | "CURLAUTH_ANYSAFE" => return true,
+                "CURLSSH_AUTH_ANY" | "CURLSSH_AUTH_DEFAULT" => return true,
+                _ => {}
+            }
+        }
+        if version < (8, 14) {
+            match s {
+                "CURLWS_NOAUTOPONG" => return true,
                 _ => {}
             }
         }
         if version < (8, 10) {
             match s {
                 "CURLVERSION_TWELFTH" | "CURLVERSION_NOW" => return true,
+                "CURLINFO_POSTTRANSFER_TIME_T" => return true,
+                _ => {}
+            }
+        }
+        if version < (8, 6) {
+            match s {
+                "CURLINFO_QUEUE_TIME_T" => return true,
+                _ => {}
+            }
+        }
+        if version < (8, 2) {
+            match s {
+                "CURLINFO_XFER_ID" | "CURLINFO_CONN_ID" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 88) {
+            match s {
+                "CURL_HTTP_VERSION_3ONLY" => return true,
                 _ => {}
             }
         }
@@ -89,6 +133,45 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 86) {
+            if s.starts_with("CURLWS_") || s == "CURLOPT_WS_OPTIONS" {
+                return true;
+            }
+        }
+        if version < (7, 83) {
+            if s.starts_with("CURLH_") || s.starts_with("CURLHE_") {
+                return true;
+            }
+        }
+        if version < (7, 81) {
+            match s {
+                "CURLUE_NO_ZONEID"
+                | "CURLUE_BAD_FILE_URL"
+                | "CURLUE_BAD_FRAGMENT"
+                | "CURLUE_BAD_HOSTNAME"
+                | "CURLUE_BAD_IPV6"
+                | "CURLUE_BAD_LOGIN"
+                | "CURLUE_BAD_PASSWORD"
+                | "CURLUE_BAD_PATH"
+                | "CURLUE_BAD_QUERY"
+                | "CURLUE_BAD_SCHEME"
+                | "CURLUE_BAD_SLASHES"
+                | "CURLUE_BAD_USER" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 80) {
+            match s {
+                "CURLOPT_SSH_HOST_PUBLIC_KEY_SHA256" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 78) {
+            match s {
+                "CURLU_ALLOW_SPACE" => return true,
+                _ => {}
+            }
+        }
         if version < (7, 77) {
             match s {
                 "CURLVERSION_TENTH"
@@ -119,9 +202,23 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 74) {
+            if s.starts_with("CURLHSTS_")
+                || s.starts_with("CURLSTS_")
+                || s.starts_with("CURLOPT_HSTS")
+            {
+                return true;
+            }
+        }
+        if version < (7, 73) {
+            match s {
+                "CURLKHSTAT_FINE_REPLACE" => return true,
+                _ => {}
+            }
+        }
         if version < (7, 72) {
             match s {
-                "CURLVERSION_EIGHTH" => return true,
+                "CURLVERSION_EIGHTH" | "CURL_PUSH_ERROROUT" => return true,
                 _ => {}
             }
         }
@@ -150,6 +247,12 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 69) {
+            match s {
+                "CURLOPT_MAIL_RCPT_ALLOWFAILS" => return true,
+                _ => {}
+            }
+        }
         if version < (7, 68) {
             match s {
                 "CURLSSLOPT_NO_PARTIALCHAIN" => return true,
@@ -162,6 +265,12 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 67) {
+            match s {
+                "CURLU_NO_AUTHORITY" => return true,
+                _ => {}
+            }
+        }
         if version < (7, 66) {
             match s {
                 "CURL_HTTP_VERSION_3" => return true,
@@ -172,6 +281,9 @@ fn main() {
         if version < (7, 65) {
             match s {
                 "CURLVERSION_SIXTH" => return true,
+                "CURLUPART_ZONEID" => return true,
+                "CURLOPT_ALTSVC" | "CURLOPT_ALTSVC_CTRL" => return true,
+                s if s.starts_with("CURLALTSVC_") => return true,
                 _ => {}
             }
         }
@@ -188,7 +300,16 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 63) {
+            match s {
+                "CURLOPT_CURLU" => return true,
+                _ => {}
+            }
+        }
         if version < (7, 62) {
+            if s.starts_with("CURLUE_") || s.starts_with("CURLUPART_") || s.starts_with("CURLU_") {
+                return true;
+            }
             match s {
                 "CURLOPT_DOH_URL" => return true,
                 "CURLOPT_UPLOAD_BUFFERSIZE" => return true,
@@ -199,6 +320,8 @@ fn main() {
             match s {
                 "CURLOPT_PIPEWAIT" => return true,
                 "CURLE_PEER_FAILED_VERIFICATION" => return true,
+                "CURL_LOCK_DATA_PSL" => return true,
+                s if s.starts_with("CURLINFO_") && s.ends_with("_TIME_T") => return true,
                 _ => {}
             }
         }
@@ -208,6 +331,28 @@ fn main() {
                 _ => {}
             }
         }
+        if version < (7, 58) {
+            match s {
+                "CURLKHTYPE_ECDSA" | "CURLKHTYPE_ED25519" | "CURLSSH_AUTH_GSSAPI" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 56) {
+            match s {
+                "CURLOPT_MIMEPOST" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 55) {
+            match s {
+                "CURLINFO_OFF_T"
+                | "CURLINFO_SIZE_UPLOAD_T"
+                | "CURLINFO_SIZE_DOWNLOAD_T"
+                | "CURLINFO_SPEED_DOWNLOAD_T"
+                | "CURLINFO_SPEED_UPLOAD_T" => return true,
+                _ => {}
+            }
+        }
         if version < (7, 54) {
             match s {
                 "CURL_SSLVERSION_TLSv1_3" | "CURLOPT_PROXY_SSLCERT" | "CURLOPT_PROXY_SSLKEY" => {
@@ -258,6 +403,13 @@ fn main() {
                 return true;
             }
         }
+        if version < (7, 37) {
+            match s {
+                "CURLOPT_PROXYHEADER" | "CURLOPT_HEADEROPT" => return true,
+                s if s.starts_with("CURLHEADER_") => return true,
+                _ => {}
+            }
+        }
         if version < (7, 25) {
             match s {
                 "CURLSSLOPT_ALLOW_BEAST" => return true,
@@ -281,8 +433,38 @@ fn main() {
         }
     });
 
+    cfg.skip_fn(move |s| {
+        if version < (7, 80) {
+            match s {
+                "curl_url_strerror" => return true,
+                _ => {}
+            }
+        }
+        if version < (7, 62) {
+            if s.starts_with("curl_url") {
+                return true;
+            }
+        }
+        if version < (7, 56) {
+            if s.starts_with("curl_mime_") {
+                return true;
+            }
+        }
+
+        false
+    });
+
+    // Bit-fields can't be addressed, see `curl_hstsentry` in curl-sys.
+    cfg.skip_field(|s, field| s == "curl_hstsentry" && field == "includeSubDomains");
+    cfg.skip_field_type(|s, field| s == "curl_hstsentry" && field == "includeSubDomains");
+
     if cfg!(target_env = "msvc") {
         cfg.skip_fn_ptrcheck(|s| s.starts_with("curl_"));
+    } else {
+        cfg.skip_fn_ptrcheck(move |s| {
+            // Older versions take a mutable handle in these.
+            version < (8, 0) && (s == "curl_url_get" || s == "curl_url_dup")
+        });
     }
 
     cfg.generate("../curl-sys/lib.rs", "all.rs");
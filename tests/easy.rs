@@ -13,7 +13,7 @@ macro_rules! t {
     };
 }
 
-use curl::easy::{Easy, Easy2, List, ReadError, Transfer, WriteError};
+use curl::easy::{Easy, Easy2, List, ReadError, Share, Transfer, WriteError};
 use curl::{Error, Version};
 
 use crate::server::Server;
@@ -231,6 +231,48 @@ fn progress() {
     assert_eq!(dl, 6.0);
 }
 
+#[test]
+fn xferinfo() {
+    use curl::easy::Handler;
+
+    struct Progress {
+        hits: u32,
+        dl: (u64, u64),
+    }
+
+    impl Handler for Progress {
+        fn progress(&mut self, _: f64, _: f64, _: f64, _: f64) -> bool {
+            panic!("progress called instead of xferinfo");
+        }
+
+        fn xferinfo(&mut self, dltotal: u64, dlnow: u64, _: u64, _: u64) -> bool {
+            self.hits += 1;
+            self.dl = (dltotal, dlnow);
+            true
+        }
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET /foo HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\nHello!");
+
+    let mut handle = Easy2::new(Progress {
+        hits: 0,
+        dl: (0, 0),
+    });
+    t!(handle.url(&s.url("/foo")));
+    t!(handle.progress(true));
+    t!(handle.perform());
+    assert!(handle.get_ref().hits > 0);
+    assert_eq!(handle.get_ref().dl, (6, 6));
+}
+
 #[test]
 fn headers() {
     let s = Server::new();
@@ -359,6 +401,57 @@ fn proxy() {
     t!(h.perform());
 }
 
+#[test]
+fn proxy_headers() {
+    let s = Server::new();
+    s.receive(
+        "\
+         GET http://example.com/ HTTP/1.1\r\n\
+         Host: example.com\r\n\
+         Accept: */*\r\n\
+         Foo: bar\r\n\
+         Proxy-Foo: bar\r\n\
+         \r\n",
+    );
+    s.send(
+        "\
+         HTTP/1.1 200 OK\r\n\
+         \r\n",
+    );
+
+    let mut headers = List::new();
+    t!(headers.append("Foo: bar"));
+    let mut proxy_headers = List::new();
+    t!(proxy_headers.append("Proxy-Foo: bar"));
+    let mut h = handle();
+    t!(h.url("http://example.com/"));
+    t!(h.proxy(&s.url("/")));
+    t!(h.header_opt(true));
+    t!(h.http_headers(headers));
+    t!(h.proxy_headers(proxy_headers));
+    t!(h.perform());
+
+    // With unified headers the proxy list is ignored.
+    let s = Server::new();
+    s.receive(
+        "\
+         GET http://example.com/ HTTP/1.1\r\n\
+         Host: example.com\r\n\
+         Accept: */*\r\n\
+         Foo: bar\r\n\
+         \r\n",
+    );
+    s.send(
+        "\
+         HTTP/1.1 200 OK\r\n\
+         \r\n",
+    );
+
+    t!(h.proxy(&s.url("/")));
+    t!(h.header_opt(false));
+    t!(h.perform());
+}
+
 #[test]
 #[ignore] // fails on newer curl versions? seems benign
 fn noproxy() {
@@ -458,6 +551,45 @@ fn accept_encoding() {
     t!(h.perform());
 }
 
+#[test]
+fn accept_encoding_all() {
+    let version = Version::get();
+    let mut encodings = Vec::new();
+    if version.feature_libz() {
+        encodings.extend(["deflate", "gzip"]);
+    }
+    if version.feature_brotli() {
+        encodings.push("br");
+    }
+    if version.feature_zstd() {
+        encodings.push("zstd");
+    }
+    if encodings.is_empty() {
+        encodings.push("identity");
+    }
+
+    let s = Server::new();
+    s.receive(&format!(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         Accept-Encoding: {}\r\n\
+         \r\n",
+        encodings.join(", ")
+    ));
+    s.send(
+        "\
+         HTTP/1.1 200 OK\r\n\
+         \r\n",
+    );
+
+    let mut h = handle();
+    t!(h.url(&s.url("/")));
+    t!(h.accept_encoding(""));
+    t!(h.perform());
+}
+
 #[test]
 fn follow_location() {
     let s1 = Server::new();
@@ -719,6 +851,53 @@ fn url_encoding() {
     assert_eq!(h.url_decode(""), b"");
 }
 
+#[test]
+fn share() {
+    let s1 = Server::new();
+    s1.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s1.send(
+        "\
+         HTTP/1.1 200 OK\r\n\
+         Set-Cookie: foo=bar\r\n\
+         \r\n",
+    );
+    let s2 = Server::new();
+    s2.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         Cookie: foo=bar\r\n\
+         \r\n",
+    );
+    s2.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut share = Share::new();
+    t!(share.cookie(true));
+    t!(share.dns(true));
+
+    let mut a = handle();
+    t!(a.share(&share));
+    t!(a.cookie_file("/dev/null"));
+    t!(a.url(&s1.url("/")));
+    t!(a.perform());
+
+    // The second handle only sees the cookie through the share, which must
+    // stay alive even after our own reference goes away.
+    let mut b = handle();
+    t!(b.share(&share));
+    t!(b.cookie_file("/dev/null"));
+    t!(b.url(&s2.url("/")));
+    drop(share);
+    t!(b.perform());
+}
+
 #[test]
 fn getters() {
     let s = Server::new();
@@ -754,6 +933,35 @@ fn getters() {
     // assert_eq!(cookies.len(), 1);
 }
 
+#[test]
+fn stats() {
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+
+    let mut h = handle();
+    t!(h.url(&s.url("/")));
+    t!(h.write_function(sink));
+    t!(h.perform());
+
+    let stats = t!(h.stats());
+    assert_eq!(stats.download_size(), 5);
+    assert_eq!(stats.upload_size(), 0);
+    assert_eq!(stats.header_size(), t!(h.header_size()));
+    assert_eq!(stats.request_size(), t!(h.request_size()));
+    assert_eq!(stats.num_connects(), 1);
+    assert_eq!(stats.redirect_count(), 0);
+    assert!(stats.connect_time() >= stats.namelookup_time());
+    assert!(stats.total_time() >= stats.starttransfer_time());
+    assert!(stats.starttransfer_time() >= stats.pretransfer_time());
+}
+
 #[test]
 #[should_panic]
 fn panic_in_callback() {
@@ -952,6 +1160,100 @@ fn test_upkeep() {
     t!(handle.upkeep());
 }
 
+#[cfg(feature = "header_7_83_0")]
+#[test]
+fn structured_headers() {
+    use curl::easy::HeaderOrigin;
+
+    let s2 = Server::new();
+    s2.receive(
+        "\
+         GET /next HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s2.send(
+        "\
+         HTTP/1.1 103 Early Hints\r\n\
+         Link: </style.css>\r\n\
+         \r\n\
+         HTTP/1.1 200 OK\r\n\
+         Set-Cookie: a=1\r\n\
+         Set-Cookie: b=2\r\n\
+         X-Folded: first\r\n \
+         second\r\n\
+         Transfer-Encoding: chunked\r\n\
+         \r\n\
+         5\r\nhello\r\n\
+         0\r\n\
+         X-Checksum: abc\r\n\
+         \r\n",
+    );
+    let s1 = Server::new();
+    s1.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s1.send(&format!(
+        "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\n\r\n",
+        s2.url("/next")
+    ));
+
+    let mut handle = handle();
+    t!(handle.url(&s1.url("/")));
+    t!(handle.follow_location(true));
+    t!(handle.write_function(sink));
+    t!(handle.perform());
+
+    let mut origin = HeaderOrigin::new();
+    origin.header(true);
+
+    let cookie = t!(handle.header("set-cookie", 1, &origin, None)).unwrap();
+    assert_eq!(cookie.name(), "Set-Cookie");
+    assert_eq!(cookie.value(), b"b=2");
+    assert_eq!(cookie.amount(), 2);
+    assert_eq!(cookie.index(), 1);
+    assert!(cookie.origin().is_header());
+    let err = handle.header("set-cookie", 2, &origin, None).unwrap_err();
+    assert!(err.is_bad_index());
+
+    let folded = t!(handle.header("X-Folded", 0, &origin, None)).unwrap();
+    assert_eq!(folded.value_str(), Some("first second"));
+    assert!(t!(handle.header("X-Missing", 0, &origin, None)).is_none());
+
+    // The first request was redirected.
+    let location = t!(handle.header("Location", 0, &origin, Some(0))).unwrap();
+    assert_eq!(location.value_str(), Some(&s2.url("/next")[..]));
+    let err = handle.header("Location", 0, &origin, Some(5)).unwrap_err();
+    assert!(err.is_no_request());
+
+    let mut trailer = HeaderOrigin::new();
+    trailer.trailer(true);
+    let checksum = t!(handle.header("X-Checksum", 0, &trailer, None)).unwrap();
+    assert!(checksum.origin().is_trailer());
+    assert_eq!(checksum.value(), b"abc");
+
+    let mut informational = HeaderOrigin::new();
+    informational.informational(true);
+    let link = t!(handle.header("Link", 0, &informational, None)).unwrap();
+    assert!(link.origin().is_informational());
+
+    let names = handle
+        .headers(&origin, None)
+        .map(|h| h.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["Set-Cookie", "Set-Cookie", "X-Folded", "Transfer-Encoding"]
+    );
+    origin.trailer(true).informational(true);
+    assert_eq!(handle.headers(&origin, None).count(), 6);
+}
+
 #[test]
 fn path_as_is() {
     let s = Server::new();
@@ -1037,3 +1339,609 @@ fn test_timeout() {
             .unwrap()
     );
 }
+
+#[test]
+fn hsts_callbacks() {
+    use curl::easy::{HstsCtrl, HstsEntry};
+    use std::sync::{Arc, Mutex};
+
+    if !Version::get().feature_hsts() {
+        return;
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut subdomains = HstsEntry::new("example.com");
+    subdomains
+        .set_include_subdomains(true)
+        .set_expire(Some("20991231 23:59:59"));
+    let mut expired = HstsEntry::new("example.net");
+    expired.set_expire(Some("20000101 00:00:00"));
+    let mut entries = vec![subdomains.clone(), expired, HstsEntry::new("example.org")].into_iter();
+
+    let written = Arc::new(Mutex::new(Vec::new()));
+    let mut handle = handle();
+    t!(handle.hsts_ctrl(HstsCtrl::new().enable(true)));
+    t!(handle.hsts_read_function(move || entries.next()));
+    let w = written.clone();
+    t!(handle.hsts_write_function(move |entry, index, total| {
+        w.lock().unwrap().push((entry.clone(), index, total));
+        true
+    }));
+    t!(handle.url(&s.url("/")));
+    t!(handle.perform());
+    drop(handle);
+
+    let mut written = written.lock().unwrap().clone();
+    written.sort_by(|a, b| a.0.host().cmp(b.0.host()));
+    assert_eq!(
+        written,
+        vec![(subdomains, 0, 2), (HstsEntry::new("example.org"), 1, 2)]
+    );
+}
+
+#[test]
+fn alt_svc_file() {
+    use curl::easy::AltSvcCtrl;
+    use std::fs;
+
+    if !Version::get().feature_altsvc() {
+        return;
+    }
+
+    let entry = "h2 example.com 443 h2 alt.example.com 8443 \"20991231 23:59:59\" 0 0";
+    let path = std::env::temp_dir().join(format!("curl-alt-svc-{}.txt", std::process::id()));
+    t!(fs::write(&path, format!("{}\n", entry)));
+
+    // A read-only cache is left alone.
+    let mut easy = handle();
+    t!(easy.alt_svc_ctrl(AltSvcCtrl::new().h1(true).h2(true).readonly_file(true)));
+    t!(easy.alt_svc(&path));
+    drop(easy);
+    assert_eq!(t!(fs::read_to_string(&path)), format!("{}\n", entry));
+
+    // Otherwise what was loaded is written back out.
+    let mut easy = handle();
+    t!(easy.alt_svc_ctrl(AltSvcCtrl::new().h1(true).h2(true)));
+    t!(easy.alt_svc(&path));
+    drop(easy);
+    let contents = t!(fs::read_to_string(&path));
+    t!(fs::remove_file(&path));
+    assert!(contents.starts_with('#'), "{}", contents);
+    assert!(contents.contains(entry), "{}", contents);
+
+    assert!(handle().alt_svc_ctrl(&AltSvcCtrl::new()).is_err());
+}
+
+#[test]
+fn certinfo_chain_without_tls() {
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut handle = handle();
+    t!(handle.url(&s.url("/")));
+    t!(handle.certinfo(true));
+    t!(handle.perform());
+    assert!(t!(handle.certinfo_chain()).is_empty());
+}
+
+#[test]
+fn try_clone() {
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Foo: bar\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+
+    let mut custom = List::new();
+    t!(custom.append("Foo: bar"));
+    t!(custom.append("Accept:"));
+    let mut template = handle();
+    t!(template.url(&s.url("/")));
+    t!(template.http_headers(custom));
+    t!(template.write_function(|_| panic!("template callback called")));
+
+    let mut h = t!(template.try_clone());
+    drop(template);
+    let body = Rc::new(RefCell::new(Vec::new()));
+    let mut h = h.transfer();
+    t!(h.write_function(|data| {
+        body.borrow_mut().extend_from_slice(data);
+        Ok(data.len())
+    }));
+    t!(h.perform());
+    drop(h);
+    assert_eq!(*body.borrow(), b"hello");
+}
+
+#[test]
+fn try_clone_mime_reader() {
+    use curl::easy::Mime;
+    use std::io::Cursor;
+
+    let mut mime = Mime::new();
+    t!(mime.add_part().data("hello".as_bytes()));
+    let mut h = handle();
+    t!(h.mime_post(mime));
+    t!(h.try_clone());
+
+    let mut mime = Mime::new();
+    t!(mime
+        .add_part()
+        .data_reader(Some(5), Cursor::new(b"hello".to_vec())));
+    t!(h.mime_post(mime));
+    assert!(h.try_clone().is_err());
+}
+
+#[test]
+fn smtp() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    if !Version::get().protocols().any(|p| p == "smtp") {
+        return;
+    }
+
+    // A small stand-in for an SMTP server which plays back a fixed dialog.
+    let listener = t!(TcpListener::bind("127.0.0.1:0"));
+    let port = t!(listener.local_addr()).port();
+    let server = thread::spawn(move || {
+        let (socket, _) = t!(listener.accept());
+        let mut reader = BufReader::new(t!(socket.try_clone()));
+        let mut socket = socket;
+        let dialog = [
+            ("", "220 localhost ESMTP\r\n"),
+            ("EHLO example.com", "250-localhost\r\n250 AUTH PLAIN\r\n"),
+            ("AUTH PLAIN", "334 \r\n"),
+            ("AHVzZXIAcGFzcw==", "235 Accepted\r\n"),
+            ("MAIL FROM:<alice@example.com> AUTH=<>", "250 OK\r\n"),
+            ("RCPT TO:<bob@example.com>", "250 OK\r\n"),
+            ("RCPT TO:<nobody@example.com>", "550 No such user\r\n"),
+            ("DATA", "354 Go ahead\r\n"),
+            ("Subject: hi", ""),
+            ("", ""),
+            ("hello", ""),
+            (".", "250 Queued\r\n"),
+            ("QUIT", "221 Bye\r\n"),
+        ];
+        for (i, (expected, reply)) in dialog.iter().enumerate() {
+            if i > 0 {
+                let mut line = String::new();
+                t!(reader.read_line(&mut line));
+                assert_eq!(line, format!("{}\r\n", expected));
+            }
+            t!(socket.write_all(reply.as_bytes()));
+        }
+    });
+
+    let mut rcpt = List::new();
+    t!(rcpt.append("<bob@example.com>"));
+    t!(rcpt.append("<nobody@example.com>"));
+    let mut body = "Subject: hi\r\n\r\nhello\r\n".as_bytes();
+
+    let mut h = handle();
+    t!(h.url(&format!("smtp://127.0.0.1:{}/example.com", port)));
+    t!(h.username("user"));
+    t!(h.password("pass"));
+    t!(h.login_options("AUTH=PLAIN"));
+    t!(h.mail_from("<alice@example.com>"));
+    t!(h.mail_auth(""));
+    t!(h.mail_rcpt(rcpt));
+    t!(h.mail_rcpt_allowfails(true));
+    t!(h.upload(true));
+    let mut transfer = h.transfer();
+    t!(transfer.read_function(|buf| Ok(body.read(buf).unwrap())));
+    t!(transfer.perform());
+    drop(transfer);
+
+    // The connection is only closed with a `QUIT` once the handle is gone.
+    drop(h);
+    t!(server.join());
+}
+
+#[test]
+fn ssh_options() {
+    use curl::easy::SshAuth;
+
+    let sftp = Version::get().protocols().any(|p| p == "sftp");
+
+    let mut handle = handle();
+    let mut auth = SshAuth::new();
+    auth.publickey(true).agent(true);
+    let results = [
+        handle.ssh_auth_types(&auth),
+        handle.ssh_public_keyfile("id_ed25519.pub"),
+        handle.ssh_private_keyfile("id_ed25519"),
+        handle.ssh_knownhosts("known_hosts"),
+    ];
+    for result in results {
+        assert_eq!(result.is_ok(), sftp, "{:?}", result);
+    }
+
+    // Only supported by libcurl 7.80.0 and later.
+    let sha256 = handle.ssh_host_public_key_sha256("AAAA");
+    if !sftp {
+        assert!(sha256.is_err());
+    }
+}
+
+#[test]
+fn http3_versions() {
+    use curl::easy::HttpVersion;
+
+    let http3 = Version::get().feature_http3();
+    let mut handle = handle();
+    for version in [HttpVersion::V3, HttpVersion::V3Only] {
+        let result = handle.http_version(version);
+        assert_eq!(result.is_ok(), http3, "{:?}: {:?}", version, result);
+    }
+}
+
+#[test]
+fn dns_options() {
+    let ares = Version::get().ares_version().is_some();
+
+    let mut h = handle();
+    let results = [
+        h.dns_interface("lo"),
+        h.dns_local_ip4("127.0.0.1"),
+        h.dns_local_ip6("::1"),
+    ];
+    for result in results {
+        assert_eq!(result.is_ok(), ares, "{:?}", result);
+    }
+}
+
+#[test]
+fn resolve_entries() {
+    use curl::easy::ResolveEntry;
+
+    let mut removal = ResolveEntry::removal("example.test", 80);
+    assert_eq!(removal.to_string(), "-example.test:80");
+    let mut entry = ResolveEntry::new("example.test", 80);
+    entry
+        .add_address("::1".parse().unwrap())
+        .add_address([127, 0, 0, 1].into());
+    assert_eq!(entry.to_string(), "example.test:80:[::1],127.0.0.1");
+    assert_eq!(
+        entry.set_transient(true).to_string(),
+        "+example.test:80:[::1],127.0.0.1"
+    );
+    removal.set_transient(true);
+    assert_eq!(removal.to_string(), "-example.test:80");
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: example.test:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let port = s.addr().port();
+    let mut entry = ResolveEntry::new("example.test", port);
+    entry.add_address([127, 0, 0, 1].into());
+
+    let mut h = handle();
+    t!(h.url(&format!("http://example.test:{}/", port)));
+    t!(h.resolve_entries(&[entry]));
+    t!(h.perform());
+    assert_eq!(t!(h.response_code()), 200);
+}
+
+#[test]
+fn resolve_handler() {
+    use curl::easy::{Handler, ResolveEntry};
+
+    struct Discovery {
+        port: u16,
+        urls: Vec<String>,
+    }
+
+    impl Handler for Discovery {
+        fn resolve(&mut self, url: &str) -> Vec<ResolveEntry> {
+            self.urls.push(url.to_string());
+            let mut entry = ResolveEntry::new("service.test", self.port);
+            entry.add_address([127, 0, 0, 1].into());
+            vec![entry]
+        }
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: service.test:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let port = s.addr().port();
+    let url = format!("http://service.test:{}/", port);
+    let mut h = Easy2::new(Discovery {
+        port,
+        urls: Vec::new(),
+    });
+    t!(h.url(&url));
+    t!(h.perform());
+    assert_eq!(t!(h.response_code()), 200);
+    assert_eq!(h.get_ref().urls, [url]);
+}
+
+#[test]
+fn sockopt() {
+    use curl::easy::{Handler, SockOptResult, SockType};
+    use socket2::SockRef;
+
+    #[derive(Default)]
+    struct Sockets {
+        purposes: Vec<SockType>,
+        reject: bool,
+    }
+
+    impl Handler for Sockets {
+        fn sockopt(&mut self, socket: &SockRef, purpose: SockType) -> SockOptResult {
+            self.purposes.push(purpose);
+            if self.reject {
+                return SockOptResult::Error;
+            }
+            t!(socket.set_recv_buffer_size(64 * 1024));
+            t!(socket.set_tcp_nodelay(true));
+            SockOptResult::Ok
+        }
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut h = Easy2::new(Sockets::default());
+    t!(h.url(&s.url("/")));
+    t!(h.perform());
+    assert_eq!(t!(h.response_code()), 200);
+    assert_eq!(h.get_ref().purposes, [SockType::Connection]);
+
+    let mut h = Easy2::new(Sockets {
+        reject: true,
+        ..Sockets::default()
+    });
+    t!(h.url(&s.url("/")));
+    let err = h.perform().unwrap_err();
+    assert!(err.is_aborted_by_callback(), "{:?}", err);
+    assert_eq!(h.get_ref().purposes, [SockType::Connection]);
+}
+
+#[cfg(unix)]
+#[test]
+fn open_close_socket() {
+    use std::net::SocketAddr;
+    use std::os::raw::c_int;
+    use std::os::unix::prelude::*;
+
+    use curl::easy::{Handler, SockType};
+    use socket2::Socket;
+
+    struct Sockets {
+        opened: Vec<(SockType, Option<SocketAddr>)>,
+        closed: Rc<Cell<usize>>,
+    }
+
+    impl Handler for Sockets {
+        fn open_socket_for(
+            &mut self,
+            family: c_int,
+            socktype: c_int,
+            protocol: c_int,
+            purpose: SockType,
+            address: Option<SocketAddr>,
+        ) -> Option<curl_sys::curl_socket_t> {
+            self.opened.push((purpose, address));
+            let socket = Socket::new(family.into(), socktype.into(), Some(protocol.into()));
+            Some(t!(socket).into_raw_fd())
+        }
+
+        fn close_socket(&mut self, socket: curl_sys::curl_socket_t) {
+            self.closed.set(self.closed.get() + 1);
+            drop(unsafe { Socket::from_raw_fd(socket) });
+        }
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+
+    let closed = Rc::new(Cell::new(0));
+    let mut h = Easy2::new(Sockets {
+        opened: Vec::new(),
+        closed: closed.clone(),
+    });
+    t!(h.url(&s.url("/")));
+    t!(h.perform());
+    assert_eq!(
+        h.get_ref().opened,
+        [(SockType::Connection, Some(*s.addr()))]
+    );
+
+    // The connection is kept around for reuse until the handle goes away.
+    assert_eq!(closed.get(), 0);
+    drop(h);
+    assert_eq!(closed.get(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn close_socket_with_dns_share() {
+    use std::os::unix::prelude::*;
+
+    use curl::easy::Handler;
+    use socket2::Socket;
+
+    struct Sockets(Rc<Cell<usize>>);
+
+    impl Handler for Sockets {
+        fn close_socket(&mut self, socket: curl_sys::curl_socket_t) {
+            self.0.set(self.0.get() + 1);
+            drop(unsafe { Socket::from_raw_fd(socket) });
+        }
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+
+    // Only a share of the connection pool keeps sockets from the handler.
+    let mut share = Share::new();
+    t!(share.dns(true));
+    let closed = Rc::new(Cell::new(0));
+    let mut h = Easy2::new(Sockets(closed.clone()));
+    t!(h.share(&share));
+    t!(h.url(&s.url("/")));
+    t!(h.perform());
+    drop(h);
+    assert_eq!(closed.get(), 1);
+}
+
+#[test]
+fn reader() {
+    use std::io::BufRead;
+
+    let body = (0..20_000)
+        .map(|i| format!("line {}\n", i))
+        .collect::<String>();
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send(&format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    ));
+
+    let mut h = handle();
+    t!(h.url(&s.url("/")));
+    t!(h.write_function(|_| panic!("the body should go to the reader")));
+    {
+        // Reading in small pieces keeps pausing the transfer.
+        let mut reader = h.reader();
+        let mut buf = [0; 7];
+        let mut read = Vec::new();
+        loop {
+            let n = t!(reader.read(&mut buf));
+            if n == 0 {
+                break;
+            }
+            read.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(read, body.as_bytes());
+        assert_eq!(t!(reader.get_ref().response_code()), 200);
+        assert_eq!(t!(reader.fill_buf()), b"");
+    }
+    assert_eq!(t!(h.response_code()), 200);
+}
+
+#[test]
+fn into_reader() {
+    use std::io::BufRead;
+
+    use curl::easy::Handler;
+
+    #[derive(Default)]
+    struct Headers(Vec<String>);
+
+    impl Handler for Headers {
+        fn write(&mut self, _: &[u8]) -> Result<usize, WriteError> {
+            panic!("the body should go to the reader")
+        }
+
+        fn header(&mut self, data: &[u8]) -> bool {
+            self.0.push(String::from_utf8_lossy(data).into_owned());
+            true
+        }
+    }
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 12\r\n\r\nfirst\nsecond");
+
+    let mut h = Easy2::new(Headers::default());
+    t!(h.url(&s.url("/")));
+    let mut reader = h.into_reader();
+    let lines = t!((&mut reader).lines().collect::<Result<Vec<_>, _>>());
+    assert_eq!(lines, ["first", "second"]);
+
+    let h = reader.into_inner();
+    assert_eq!(t!(h.response_code()), 200);
+    assert_eq!(h.get_ref().0[0], "HTTP/1.1 200 OK\r\n");
+    assert!(h
+        .get_ref()
+        .0
+        .contains(&"Content-Length: 12\r\n".to_string()));
+}
+
+#[test]
+fn reader_error() {
+    let port = {
+        let listener = t!(std::net::TcpListener::bind("127.0.0.1:0"));
+        t!(listener.local_addr()).port()
+    };
+
+    let mut h = handle();
+    t!(h.url(&format!("http://127.0.0.1:{}/", port)));
+    let mut body = Vec::new();
+    let err = h.reader().read_to_end(&mut body).unwrap_err();
+    let err = err.get_ref().unwrap().downcast_ref::<Error>().unwrap();
+    assert!(err.is_couldnt_connect(), "{:?}", err);
+    assert!(body.is_empty());
+}
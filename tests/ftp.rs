@@ -0,0 +1,363 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+use curl::easy::WriteError;
+use curl::easy::{
+    ChunkAction, Easy, Easy2, FileInfo, FileType, FtpCreateMissingDirs, FtpFileMethod, Handler,
+    List,
+};
+use curl::Version;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+fn ftp_enabled() -> bool {
+    Version::get().protocols().any(|p| p == "ftp")
+}
+
+type Files = &'static [(&'static str, &'static str)];
+type Received = (Vec<String>, Vec<u8>);
+
+/// A small stand-in for an FTP server which answers whatever it's asked on a
+/// single control connection, serving a directory holding `files`.
+///
+/// Returns the port it listens on and a handle yielding the commands it
+/// received along with anything uploaded to it.
+fn serve(files: Files) -> (u16, JoinHandle<Received>) {
+    let listener = t!(TcpListener::bind("127.0.0.1:0"));
+    let port = t!(listener.local_addr()).port();
+    let handle = thread::spawn(move || {
+        let (socket, _) = t!(listener.accept());
+        let mut reader = BufReader::new(t!(socket.try_clone()));
+        let mut socket = socket;
+        let mut commands = Vec::new();
+        let mut uploaded = Vec::new();
+        let mut passive = None;
+
+        t!(socket.write_all(b"220 Welcome\r\n"));
+        loop {
+            let mut line = String::new();
+            if t!(reader.read_line(&mut line)) == 0 {
+                break;
+            }
+            let command = line.trim_end().to_string();
+            let verb = command.split(' ').next().unwrap().to_string();
+            let arg = command.split(' ').nth(1).unwrap_or("");
+            let file = files.iter().find(|(name, _)| *name == arg);
+            commands.push(command);
+
+            let reply = match &verb[..] {
+                "USER" => "331 Password required".to_string(),
+                "PASS" => "230 Logged in".to_string(),
+                "PWD" => "257 \"/home/rust\" is the current directory".to_string(),
+                "EPSV" | "PASV" => {
+                    let data = t!(TcpListener::bind("127.0.0.1:0"));
+                    let port = t!(data.local_addr()).port();
+                    passive = Some(data);
+                    if verb == "EPSV" {
+                        format!("229 Entering Extended Passive Mode (|||{}|)", port)
+                    } else {
+                        format!(
+                            "227 Entering Passive Mode (127,0,0,1,{},{})",
+                            port >> 8,
+                            port & 0xff
+                        )
+                    }
+                }
+                "SIZE" => match file {
+                    Some((_, contents)) => format!("213 {}", contents.len()),
+                    None => "550 No such file".to_string(),
+                },
+                "LIST" | "NLST" | "RETR" | "STOR" | "APPE" => {
+                    t!(socket.write_all(b"150 Opening data connection\r\n"));
+                    let (mut conn, _) = t!(passive.take().unwrap().accept());
+                    match &verb[..] {
+                        "STOR" | "APPE" => drop(t!(conn.read_to_end(&mut uploaded))),
+                        "RETR" => t!(conn.write_all(file.unwrap().1.as_bytes())),
+                        _ => {
+                            for (name, contents) in files {
+                                let entry = if verb == "NLST" {
+                                    format!("{}\n", name)
+                                } else {
+                                    format!(
+                                        "-rw-r--r-- 1 rust rust {} Jan 01 12:00 {}\r\n",
+                                        contents.len(),
+                                        name
+                                    )
+                                };
+                                t!(conn.write_all(entry.as_bytes()));
+                            }
+                        }
+                    }
+                    drop(conn);
+                    "226 Transfer complete".to_string()
+                }
+                "QUIT" => {
+                    t!(socket.write_all(b"221 Bye\r\n"));
+                    break;
+                }
+                _ => "200 OK".to_string(),
+            };
+            t!(socket.write_all(format!("{}\r\n", reply).as_bytes()));
+        }
+        (commands, uploaded)
+    });
+    (port, handle)
+}
+
+fn position(commands: &[String], command: &str) -> usize {
+    match commands.iter().position(|c| c == command) {
+        Some(i) => i,
+        None => panic!("{:?} wasn't sent, got {:?}", command, commands),
+    }
+}
+
+#[test]
+fn list_with_quotes() {
+    if !ftp_enabled() {
+        return;
+    }
+
+    let (port, server) = serve(&[("a.txt", ""), ("b.txt", "")]);
+
+    let mut quote = List::new();
+    t!(quote.append("SITE QUOTE"));
+    let mut postquote = List::new();
+    t!(postquote.append("SITE POSTQUOTE"));
+
+    let mut body = Vec::new();
+    let mut h = Easy::new();
+    t!(h.url(&format!("ftp://127.0.0.1:{}/pub/", port)));
+    t!(h.dirlistonly(true));
+    t!(h.ftp_use_epsv(false));
+    t!(h.quote(quote));
+    t!(h.postquote(postquote));
+    {
+        let mut transfer = h.transfer();
+        t!(transfer.write_function(|data| {
+            body.extend_from_slice(data);
+            Ok(data.len())
+        }));
+        t!(transfer.perform());
+    }
+    assert_eq!(body, b"a.txt\nb.txt\n");
+    assert_eq!(t!(h.ftp_entry_path()), Some("/home/rust"));
+    drop(h);
+
+    let (commands, _) = t!(server.join());
+    assert!(!commands.iter().any(|c| c == "EPSV"));
+    let quote = position(&commands, "SITE QUOTE");
+    let cwd = position(&commands, "CWD pub");
+    let nlst = position(&commands, "NLST");
+    let postquote = position(&commands, "SITE POSTQUOTE");
+    assert!(quote < cwd, "{:?}", commands);
+    assert!(cwd < nlst, "{:?}", commands);
+    assert!(nlst < postquote, "{:?}", commands);
+}
+
+// Older versions of libcurl only send the prequote commands for file
+// transfers, not listings.
+#[test]
+fn retr_with_prequote() {
+    if !ftp_enabled() {
+        return;
+    }
+
+    let (port, server) = serve(&[("a.txt", "hello")]);
+
+    let mut prequote = List::new();
+    t!(prequote.append("SITE PREQUOTE"));
+
+    let mut body = Vec::new();
+    let mut h = Easy::new();
+    t!(h.url(&format!("ftp://127.0.0.1:{}/a.txt", port)));
+    t!(h.ftp_use_epsv(false));
+    t!(h.prequote(prequote));
+    {
+        let mut transfer = h.transfer();
+        t!(transfer.write_function(|data| {
+            body.extend_from_slice(data);
+            Ok(data.len())
+        }));
+        t!(transfer.perform());
+    }
+    assert_eq!(body, b"hello");
+    drop(h);
+
+    let (commands, _) = t!(server.join());
+    let prequote = position(&commands, "SITE PREQUOTE");
+    let retr = position(&commands, "RETR a.txt");
+    assert!(prequote < retr, "{:?}", commands);
+}
+
+#[test]
+fn append_with_single_cwd() {
+    if !ftp_enabled() {
+        return;
+    }
+
+    let (port, server) = serve(&[]);
+
+    let mut data = "hello".as_bytes();
+    let mut h = Easy::new();
+    t!(h.url(&format!("ftp://127.0.0.1:{}/pub/new/file.txt", port)));
+    t!(h.upload(true));
+    t!(h.append(true));
+    t!(h.ftp_create_missing_dirs(FtpCreateMissingDirs::Create));
+    t!(h.ftp_filemethod(FtpFileMethod::SingleCwd));
+    {
+        let mut transfer = h.transfer();
+        t!(transfer.read_function(|buf| Ok(data.read(buf).unwrap())));
+        t!(transfer.perform());
+    }
+    drop(h);
+
+    let (commands, uploaded) = t!(server.join());
+    position(&commands, "CWD pub/new");
+    position(&commands, "APPE file.txt");
+    assert_eq!(uploaded, b"hello");
+}
+
+#[derive(Default)]
+struct Wildcard {
+    body: Vec<u8>,
+    begun: Vec<(String, u32)>,
+    ended: usize,
+    matched: Vec<(String, String)>,
+    only_logs: bool,
+}
+
+impl Handler for Wildcard {
+    fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+        self.body.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn chunk_begin(&mut self, info: &FileInfo, remaining: u32) -> ChunkAction {
+        let name = info.filename().unwrap();
+        assert_eq!(info.file_type(), Some(FileType::File));
+        assert_eq!(info.perm(), Some(0o644));
+        assert_eq!(info.user(), Some("rust"));
+        assert_eq!(info.time(), Some("Jan 01 12:00"));
+        self.begun.push((name.to_string(), remaining));
+        match name {
+            "b.txt" => ChunkAction::Skip,
+            _ => {
+                assert_eq!(info.size(), Some(5));
+                ChunkAction::Ok
+            }
+        }
+    }
+
+    fn chunk_end(&mut self) -> bool {
+        self.ended += 1;
+        true
+    }
+
+    fn fnmatch(&mut self, pattern: &[u8], name: &[u8]) -> bool {
+        self.matched.push((
+            String::from_utf8_lossy(pattern).into_owned(),
+            String::from_utf8_lossy(name).into_owned(),
+        ));
+        if self.only_logs {
+            name.ends_with(b".log")
+        } else {
+            name.ends_with(b".txt")
+        }
+    }
+}
+
+const WILDCARD_FILES: Files = &[("a.txt", "hello"), ("b.txt", "world"), ("c.log", "other")];
+
+#[test]
+fn wildcard_chunks() {
+    if !ftp_enabled() {
+        return;
+    }
+
+    let (port, server) = serve(WILDCARD_FILES);
+
+    let mut h = Easy2::new(Wildcard::default());
+    t!(h.url(&format!("ftp://127.0.0.1:{}/pub/*.txt", port)));
+    t!(h.wildcard_match(true));
+    t!(h.fnmatch_function(true));
+    t!(h.perform());
+    assert_eq!(h.get_ref().body, b"hello");
+    assert_eq!(
+        h.get_ref().begun,
+        [("a.txt".to_string(), 2), ("b.txt".to_string(), 1)]
+    );
+    assert_eq!(h.get_ref().ended, 2);
+    let matched = &h.get_ref().matched;
+    assert_eq!(matched.len(), 3);
+    assert!(matched.iter().all(|(pattern, _)| pattern == "*.txt"));
+    drop(h);
+
+    let (commands, _) = t!(server.join());
+    position(&commands, "RETR a.txt");
+    assert!(!commands.iter().any(|c| c == "RETR b.txt"));
+}
+
+#[test]
+fn wildcard_custom_fnmatch() {
+    if !ftp_enabled() {
+        return;
+    }
+
+    let (port, server) = serve(WILDCARD_FILES);
+
+    let mut h = Easy2::new(Wildcard {
+        only_logs: true,
+        ..Wildcard::default()
+    });
+    t!(h.url(&format!("ftp://127.0.0.1:{}/pub/*.txt", port)));
+    t!(h.wildcard_match(true));
+    t!(h.fnmatch_function(true));
+    t!(h.perform());
+    assert_eq!(h.get_ref().body, b"other");
+    assert_eq!(h.get_ref().begun, [("c.log".to_string(), 1)]);
+    drop(h);
+    t!(server.join());
+}
+
+#[test]
+fn wildcard_default_fnmatch() {
+    if !ftp_enabled() {
+        return;
+    }
+
+    struct Collector(Vec<u8>);
+
+    impl Handler for Collector {
+        fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+            self.0.extend_from_slice(data);
+            Ok(data.len())
+        }
+    }
+
+    for (pattern, expected) in [
+        ("*", "helloworldother"),
+        ("[!a].*", "worldother"),
+        ("[[:alpha:]].l*g", "other"),
+        ("[a-b].txt", "helloworld"),
+        ("*o*", "other"),
+    ] {
+        let (port, server) = serve(WILDCARD_FILES);
+        let mut h = Easy2::new(Collector(Vec::new()));
+        t!(h.url(&format!("ftp://127.0.0.1:{}/pub/{}", port, pattern)));
+        t!(h.wildcard_match(true));
+        if let Err(e) = h.perform() {
+            panic!("{}: {}", pattern, e);
+        }
+        assert_eq!(h.get_ref().0, expected.as_bytes(), "{}", pattern);
+        drop(h);
+        t!(server.join());
+    }
+}
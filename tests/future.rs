@@ -0,0 +1,137 @@
+#![cfg(all(unix, feature = "tokio"))]
+
+use std::future::Future;
+use std::net::TcpListener;
+use std::pin::Pin;
+use std::task::{Context, Waker};
+use std::time::Duration;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+use curl::easy::{Easy2, Handler, WriteError};
+use curl::future::{Perform, Tokio};
+
+use crate::server::Server;
+mod server;
+
+#[derive(Debug)]
+struct Collector(Vec<u8>);
+
+impl Handler for Collector {
+    fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+        self.0.extend_from_slice(data);
+        Ok(data.len())
+    }
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    t!(tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build())
+}
+
+fn handle(url: &str) -> Easy2<Collector> {
+    let mut easy = Easy2::new(Collector(Vec::new()));
+    t!(easy.url(url));
+    t!(easy.timeout(Duration::new(20, 0)));
+    easy
+}
+
+fn hello_server() -> Server {
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+    s
+}
+
+#[test]
+fn get() {
+    let s = hello_server();
+    let easy = t!(runtime().block_on(Perform::new(handle(&s.url("/")), Tokio)));
+    assert_eq!(t!(easy.response_code()), 200);
+    assert_eq!(easy.get_ref().0, b"hello");
+}
+
+#[test]
+fn concurrent() {
+    let s1 = hello_server();
+    let s2 = hello_server();
+    let rt = runtime();
+    let (a, b) = rt.block_on(async {
+        let a = tokio::spawn(Perform::new(handle(&s1.url("/")), Tokio));
+        let b = tokio::spawn(Perform::new(handle(&s2.url("/")), Tokio));
+        (t!(a.await), t!(b.await))
+    });
+    assert_eq!(t!(a).get_ref().0, b"hello");
+    assert_eq!(t!(b).get_ref().0, b"hello");
+}
+
+#[test]
+fn connect_error() {
+    let addr = t!(t!(TcpListener::bind("127.0.0.1:0")).local_addr());
+    let easy = handle(&format!("http://{}/", addr));
+    let err = runtime().block_on(Perform::new(easy, Tokio)).unwrap_err();
+    assert!(err.is_couldnt_connect());
+}
+
+#[test]
+#[should_panic(expected = "`Perform` polled after completion")]
+fn poll_after_completion() {
+    let addr = t!(t!(TcpListener::bind("127.0.0.1:0")).local_addr());
+    let mut perform = Perform::new(handle(&format!("http://{}/", addr)), Tokio);
+    assert!(runtime().block_on(&mut perform).is_err());
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let _ = Pin::new(&mut perform).poll(&mut cx);
+}
+
+#[test]
+fn timeout() {
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+
+    let mut easy = handle(&s.url("/"));
+    t!(easy.timeout(Duration::from_millis(200)));
+    let err = runtime().block_on(Perform::new(easy, Tokio)).unwrap_err();
+    assert!(err.is_operation_timedout());
+}
+
+#[test]
+fn large_body() {
+    let body = "a".repeat(4 * 1024 * 1024);
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send(&format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    ));
+
+    let easy = t!(runtime().block_on(Perform::new(handle(&s.url("/")), Tokio)));
+    assert_eq!(easy.get_ref().0.len(), body.len());
+}
@@ -1,10 +1,12 @@
 #![cfg(unix)]
 
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::io::{Cursor, Read};
+use std::rc::Rc;
 use std::time::Duration;
 
-use curl::easy::{Easy, List};
+use curl::easy::{Easy, Easy2, Handler, List};
 use curl::multi::Multi;
 
 macro_rules! t {
@@ -84,6 +86,241 @@ fn smoke2() {
     assert_eq!(done, 2);
 }
 
+#[test]
+fn push_function() {
+    use curl::easy::{Easy2, Handler, WriteError};
+
+    struct Collector(Vec<u8>);
+
+    impl Handler for Collector {
+        fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+            self.0.extend_from_slice(data);
+            Ok(data.len())
+        }
+    }
+
+    let mut m = Multi::new();
+    t!(m.pipelining(false, true));
+    t!(m.push_function(|request| {
+        assert!(request.header(":path").is_some());
+        Some(request.accept(Collector(Vec::new())))
+    }));
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+
+    let mut e = Easy2::new(Collector(Vec::new()));
+    t!(e.url(&s.url("/")));
+    let e = t!(m.add2(e));
+    while t!(m.perform()) > 0 {
+        t!(m.wait(&mut [], Duration::from_secs(1)));
+    }
+
+    let mut done = 0;
+    m.messages(|msg| {
+        t!(msg.result_for2(&e).unwrap());
+        done += 1;
+    });
+    assert_eq!(done, 1);
+    assert_eq!(e.get_ref().0, b"hello");
+
+    // Nothing is pushed over HTTP/1.1.
+    assert!(m.take_pushed::<Collector>().is_empty());
+}
+
+// A minimal HTTP/2 server, speaking with prior knowledge, which answers the
+// first request with `hello` and pushes `/pushed` along with it.
+fn h2_push_server() -> (u16, std::thread::JoinHandle<()>) {
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    fn frame(ty: u8, flags: u8, stream: u32, payload: &[u8]) -> Vec<u8> {
+        let len = payload.len() as u32;
+        let mut frame = len.to_be_bytes()[1..].to_vec();
+        frame.push(ty);
+        frame.push(flags);
+        frame.extend_from_slice(&stream.to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    // A literal header field without indexing, with an indexed name.
+    fn literal(index: u8, value: &str) -> Vec<u8> {
+        let mut field = vec![index, value.len() as u8];
+        field.extend_from_slice(value.as_bytes());
+        field
+    }
+
+    let listener = t!(TcpListener::bind("127.0.0.1:0"));
+    let port = t!(listener.local_addr()).port();
+    let thread = std::thread::spawn(move || {
+        let (mut socket, _) = t!(listener.accept());
+        let mut preface = [0; 24];
+        t!(socket.read_exact(&mut preface));
+        assert_eq!(&preface, b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n");
+        t!(socket.write_all(&frame(0x4, 0, 0, &[])));
+
+        // Wait for the end of the first request.
+        loop {
+            let mut header = [0; 9];
+            t!(socket.read_exact(&mut header));
+            let len = u32::from_be_bytes([0, header[0], header[1], header[2]]);
+            let mut payload = vec![0; len as usize];
+            t!(socket.read_exact(&mut payload));
+            let (ty, flags) = (header[3], header[4]);
+            if ty == 0x4 && flags & 0x1 == 0 {
+                t!(socket.write_all(&frame(0x4, 0x1, 0, &[])));
+            }
+            if (ty == 0x0 || ty == 0x1) && flags & 0x1 != 0 {
+                break;
+            }
+        }
+
+        // :method GET, :scheme http, :authority and :path /pushed
+        let mut promise = 2u32.to_be_bytes().to_vec();
+        promise.extend_from_slice(&[0x82, 0x86]);
+        promise.extend(literal(0x01, &format!("127.0.0.1:{}", port)));
+        promise.extend(literal(0x04, "/pushed"));
+        t!(socket.write_all(&frame(0x5, 0x4, 1, &promise)));
+        // :status 200
+        t!(socket.write_all(&frame(0x1, 0x4, 2, &[0x88])));
+        t!(socket.write_all(&frame(0x0, 0x1, 2, b"pushed")));
+        t!(socket.write_all(&frame(0x1, 0x4, 1, &[0x88])));
+        t!(socket.write_all(&frame(0x0, 0x1, 1, b"hello")));
+
+        // Keep the connection open until the client is done with it.
+        let mut rest = Vec::new();
+        let _ = socket.read_to_end(&mut rest);
+    });
+    (port, thread)
+}
+
+#[test]
+fn push_function_h2() {
+    use curl::easy::{HttpVersion, WriteError};
+
+    #[derive(Debug)]
+    struct Collector(Vec<u8>);
+
+    impl Handler for Collector {
+        fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+            self.0.extend_from_slice(data);
+            Ok(data.len())
+        }
+    }
+
+    // libcurl 7.88.1 never hands the push of this server to the callback,
+    // so only run this with 8.19.0 and later, which it's known to work with.
+    let version = curl::Version::get();
+    if !version.feature_http2() || version.version_num() < 0x081300 {
+        return;
+    }
+
+    let (port, server) = h2_push_server();
+    let mut m = Multi::new();
+    t!(m.pipelining(false, true));
+    t!(m.push_function(move |request| {
+        assert_eq!(request.header(":path"), Some("/pushed"));
+        let headers = request.headers().collect::<Vec<_>>();
+        assert!(headers.contains(&(":method", "GET")));
+        let mut handle = request.accept(Collector(Vec::new()));
+        t!(handle.set_token(2));
+        Some(handle)
+    }));
+
+    let mut e = Easy2::new(Collector(Vec::new()));
+    t!(e.url(&format!("http://127.0.0.1:{}/", port)));
+    t!(e.http_version(HttpVersion::V2PriorKnowledge));
+    let mut e = t!(m.add2(e));
+    t!(e.set_token(1));
+    while t!(m.perform()) > 0 {
+        t!(m.wait(&mut [], Duration::from_secs(1)));
+    }
+
+    let mut pushed = m.take_pushed::<Collector>();
+    assert_eq!(pushed.len(), 1);
+    let mut done = Vec::new();
+    m.messages(|msg| {
+        let token = t!(msg.token());
+        if token == 1 {
+            t!(msg.result_for2(&e).unwrap());
+        } else {
+            t!(msg.result_for2(&pushed[0]).unwrap());
+        }
+        done.push(token);
+    });
+    done.sort();
+    assert_eq!(done, [1, 2]);
+    assert_eq!(e.get_ref().0, b"hello");
+    assert_eq!(pushed[0].get_ref().0, b"pushed");
+
+    let pushed = t!(m.remove2(pushed.remove(0)));
+    let url = format!("http://127.0.0.1:{}/pushed", port);
+    assert_eq!(t!(pushed.effective_url()), Some(&url[..]));
+    drop(e);
+    drop(m);
+    server.join().unwrap();
+}
+
+#[test]
+fn push_refused_with_mime_reader() {
+    use curl::easy::{HttpVersion, Mime, WriteError};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct Collector(Vec<u8>);
+
+    impl Handler for Collector {
+        fn write(&mut self, data: &[u8]) -> Result<usize, WriteError> {
+            self.0.extend_from_slice(data);
+            Ok(data.len())
+        }
+    }
+
+    if !curl::Version::get().feature_http2() {
+        return;
+    }
+
+    let (port, server) = h2_push_server();
+    let called = Arc::new(AtomicBool::new(false));
+    let mut m = Multi::new();
+    t!(m.pipelining(false, true));
+    let called2 = called.clone();
+    t!(m.push_function(move |request| {
+        called2.store(true, Ordering::SeqCst);
+        Some(request.accept(Collector(Vec::new())))
+    }));
+
+    let mut mime = Mime::new();
+    {
+        let mut part = mime.add_part();
+        t!(part.name("file"));
+        t!(part.data_reader(Some(4), Cursor::new(b"data".to_vec())));
+    }
+    let mut e = Easy::new();
+    t!(e.url(&format!("http://127.0.0.1:{}/", port)));
+    t!(e.http_version(HttpVersion::V2PriorKnowledge));
+    t!(e.mime_post(mime));
+    let e = t!(m.add(e));
+    while t!(m.perform()) > 0 {
+        t!(m.wait(&mut [], Duration::from_secs(1)));
+    }
+
+    m.messages(|msg| t!(msg.result_for(&e).unwrap()));
+    assert!(!called.load(Ordering::SeqCst));
+    assert!(m.take_pushed::<Collector>().is_empty());
+    drop(e);
+    drop(m);
+    server.join().unwrap();
+}
+
 #[test]
 fn upload_lots() {
     use curl::multi::{Events, Socket, SocketEvents};
@@ -302,3 +539,44 @@ fn pollfds() {
         assert!(waitfd.received_read());
     }
 }
+
+#[test]
+fn close_socket_after_remove() {
+    struct Sockets(Rc<Cell<usize>>);
+
+    impl Handler for Sockets {
+        fn close_socket(&mut self, socket: curl_sys::curl_socket_t) {
+            self.0.set(self.0.get() + 1);
+            unsafe {
+                libc::close(socket);
+            }
+        }
+    }
+
+    let m = Multi::new();
+
+    let s = Server::new();
+    s.receive(
+        "\
+         GET / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         \r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+
+    let closed = Rc::new(Cell::new(0));
+    let mut e = Easy2::new(Sockets(closed.clone()));
+    t!(e.url(&s.url("/")));
+    let e = t!(m.add2(e));
+    while t!(m.perform()) > 0 {
+        t!(m.wait(&mut [], Duration::from_secs(1)));
+    }
+
+    // The connection stays in the multi handle's cache, and is closed without
+    // the handler once the handle has been removed.
+    let e = t!(m.remove2(e));
+    drop(m);
+    assert_eq!(closed.get(), 0);
+    drop(e);
+}
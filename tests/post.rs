@@ -10,7 +10,9 @@ macro_rules! t {
     };
 }
 
-use curl::easy::{Easy, Form, List};
+use std::io::Cursor;
+
+use curl::easy::{Easy, Form, List, Mime};
 
 use crate::server::Server;
 mod server;
@@ -132,3 +134,117 @@ fn file() {
     t!(handle.httppost(form));
     t!(handle.perform());
 }
+
+#[test]
+fn mime_data() {
+    let s = Server::new();
+    s.receive(&format!(
+        "\
+         POST / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         Content-Length: {}\r\n\
+         Content-Type: multipart/form-data; boundary=--[..]\r\n\
+         \r\n\
+         --[..]\r\n\
+         Content-Disposition: form-data; name=\"foo\"\r\n\
+         \r\n\
+         1234\r\n\
+         --[..]\r\n",
+        multipart_boundary_size() + 6
+    ));
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut handle = handle();
+    let mut mime = Mime::new();
+    {
+        let mut part = mime.add_part();
+        t!(part.name("foo"));
+        t!(part.data(b"1234"));
+    }
+    t!(handle.url(&s.url("/")));
+    t!(handle.mime_post(mime));
+    t!(handle.perform());
+}
+
+#[test]
+fn mime_reader() {
+    let s = Server::new();
+    s.receive(
+        "\
+         POST / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         Content-Length: [..]\r\n\
+         Content-Type: multipart/form-data; boundary=--[..]\r\n\
+         \r\n\
+         --[..]\r\n\
+         Content-Disposition: form-data; name=\"foo\"; filename=\"bar.txt\"\r\n\
+         Content-Type: text/plain\r\n\
+         X-Custom: yes\r\n\
+         \r\n\
+         hello world\r\n\
+         --[..]\r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut handle = handle();
+    let mut mime = Mime::new();
+    {
+        let mut part = mime.add_part();
+        t!(part.name("foo"));
+        t!(part.filename("bar.txt"));
+        t!(part.content_type("text/plain"));
+        let mut headers = List::new();
+        t!(headers.append("X-Custom: yes"));
+        t!(part.headers(headers));
+        t!(part.data_reader(Some(11), Cursor::new(b"hello world".to_vec())));
+    }
+    t!(handle.url(&s.url("/")));
+    t!(handle.mime_post(mime));
+    t!(handle.perform());
+}
+
+#[test]
+fn mime_subparts() {
+    let s = Server::new();
+    s.receive(
+        "\
+         POST / HTTP/1.1\r\n\
+         Host: 127.0.0.1:$PORT\r\n\
+         Accept: */*\r\n\
+         Content-Length: [..]\r\n\
+         Content-Type: multipart/form-data; boundary=--[..]\r\n\
+         \r\n\
+         --[..]\r\n\
+         Content-Disposition: form-data; name=\"outer\"\r\n\
+         Content-Type: multipart/mixed; boundary=--[..]\r\n\
+         \r\n\
+         --[..]\r\n\
+         Content-Transfer-Encoding: base64\r\n\
+         \r\n\
+         aW5uZXI=\r\n\
+         --[..]\r\n\
+         \r\n\
+         --[..]\r\n",
+    );
+    s.send("HTTP/1.1 200 OK\r\n\r\n");
+
+    let mut inner = Mime::new();
+    {
+        let mut part = inner.add_part();
+        t!(part.encoder("base64"));
+        t!(part.data(b"inner"));
+    }
+
+    let mut handle = handle();
+    let mut mime = Mime::new();
+    {
+        let mut part = mime.add_part();
+        t!(part.name("outer"));
+        t!(part.subparts(inner));
+    }
+    t!(handle.url(&s.url("/")));
+    t!(handle.mime_post(mime));
+    t!(handle.perform());
+}
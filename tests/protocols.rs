@@ -17,3 +17,41 @@ fn static_with_ftp_enabled() {
         .next()
         .is_some());
 }
+
+#[cfg(feature = "static-curl")]
+#[test]
+fn static_mail_protocols() {
+    let enabled = |name| curl::Version::get().protocols().any(|p| p == name);
+    assert_eq!(enabled("imap"), cfg!(feature = "protocol-imap"));
+    assert_eq!(enabled("pop3"), cfg!(feature = "protocol-pop3"));
+    assert_eq!(enabled("smtp"), cfg!(feature = "protocol-smtp"));
+}
+
+#[cfg(all(feature = "static-curl", feature = "ssh"))]
+#[test]
+fn static_with_ssh_enabled() {
+    let version = curl::Version::get();
+    assert!(version.protocols().any(|p| p == "sftp"));
+    assert!(version.protocols().any(|p| p == "scp"));
+    assert!(version.libssh_version().is_some());
+}
+
+#[cfg(all(feature = "static-curl", feature = "http3"))]
+#[test]
+fn static_with_http3_enabled() {
+    assert!(curl::Version::get().feature_http3());
+}
+
+#[cfg(feature = "static-curl")]
+#[test]
+fn static_content_encodings() {
+    let version = curl::Version::get();
+    assert_eq!(version.feature_brotli(), cfg!(feature = "brotli"));
+    assert_eq!(version.feature_zstd(), cfg!(feature = "zstd"));
+}
+
+#[cfg(feature = "ares")]
+#[test]
+fn static_with_ares() {
+    assert!(curl::Version::get().ares_version().is_some());
+}
@@ -17,6 +17,7 @@ pub struct Server {
 enum Message {
     Read(String),
     Write(String),
+    Frame(u8, Vec<u8>),
 }
 
 enum Addr {
@@ -111,6 +112,7 @@ fn run(stream: impl Read + Write, rx: &Receiver<Message>) {
                 t!(socket.get_mut().write_all(to_write.as_bytes()));
                 return;
             }
+            Message::Frame(..) => panic!("frames can only be sent by a WebSocket server"),
         }
     }
 
@@ -134,6 +136,180 @@ fn lines_match(expected: &str, mut actual: &str) -> bool {
     actual.is_empty() || expected.ends_with("[..]")
 }
 
+// Accepts a WebSocket upgrade, sends any frames queued with `send_frame` and
+// then echoes every data frame back until either side closes the connection.
+fn run_ws(stream: impl Read + Write, rx: &Receiver<Message>) {
+    let mut socket = BufReader::new(stream);
+    let mut key = None;
+    loop {
+        let mut line = String::new();
+        t!(socket.read_line(&mut line));
+        if line == "\r\n" || line.is_empty() {
+            break;
+        }
+        let mut parts = line.splitn(2, ':');
+        let name = parts.next().unwrap();
+        if name.eq_ignore_ascii_case("Sec-WebSocket-Key") {
+            key = Some(parts.next().unwrap().trim().to_string());
+        }
+    }
+    let key = key.expect("missing Sec-WebSocket-Key header");
+    let accept = base64(&sha1(
+        format!("{}258EAFA5-E914-47DA-95CA-C5AB0DC85B11", key).as_bytes(),
+    ));
+    t!(write!(
+        socket.get_mut(),
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\
+         \r\n",
+        accept
+    ));
+    for msg in rx.try_iter() {
+        if let Message::Frame(head, payload) = msg {
+            write_frame(socket.get_mut(), head, &payload);
+            // Having sent a close frame, hang up.
+            if head & 0x0f == 0x8 {
+                return;
+            }
+        }
+    }
+
+    while let Some((head, payload)) = read_frame(&mut socket) {
+        match head & 0x0f {
+            // close
+            0x8 => {
+                write_frame(socket.get_mut(), head, &payload);
+                return;
+            }
+            // ping
+            0x9 => write_frame(socket.get_mut(), 0x80 | 0xa, &payload),
+            // pong
+            0xa => {}
+            _ => write_frame(socket.get_mut(), head, &payload),
+        }
+    }
+}
+
+fn read_frame(socket: &mut impl Read) -> Option<(u8, Vec<u8>)> {
+    let mut head = [0; 2];
+    if socket.read_exact(&mut head).is_err() {
+        return None;
+    }
+    let len = match head[1] & 0x7f {
+        126 => {
+            let mut buf = [0; 2];
+            t!(socket.read_exact(&mut buf));
+            u16::from_be_bytes(buf) as usize
+        }
+        127 => {
+            let mut buf = [0; 8];
+            t!(socket.read_exact(&mut buf));
+            u64::from_be_bytes(buf) as usize
+        }
+        n => n as usize,
+    };
+    let mut mask = [0; 4];
+    if head[1] & 0x80 != 0 {
+        t!(socket.read_exact(&mut mask));
+    }
+    let mut payload = vec![0; len];
+    t!(socket.read_exact(&mut payload));
+    for (i, b) in payload.iter_mut().enumerate() {
+        *b ^= mask[i % 4];
+    }
+    Some((head[0], payload))
+}
+
+fn write_frame(socket: &mut impl Write, head: u8, payload: &[u8]) {
+    let mut frame = vec![head];
+    if payload.len() < 126 {
+        frame.push(payload.len() as u8);
+    } else if payload.len() <= 0xffff {
+        frame.push(126);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    } else {
+        frame.push(127);
+        frame.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    }
+    frame.extend_from_slice(payload);
+    t!(socket.write_all(&frame));
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for chunk in msg.chunks(64) {
+        let mut w = [0u32; 80];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([
+                chunk[i * 4],
+                chunk[i * 4 + 1],
+                chunk[i * 4 + 2],
+                chunk[i * 4 + 3],
+            ]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, w) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a827999),
+                20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let tmp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*w);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = tmp;
+        }
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+    let mut out = [0; 20];
+    for (i, h) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&h.to_be_bytes());
+    }
+    out
+}
+
+fn base64(data: &[u8]) -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(CHARS[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
 impl Server {
     pub fn new() -> Server {
         let listener = t!(TcpListener::bind("127.0.0.1:0"));
@@ -163,6 +339,18 @@ impl Server {
         }
     }
 
+    pub fn new_ws() -> Server {
+        let listener = t!(TcpListener::bind("127.0.0.1:0"));
+        let addr = t!(listener.local_addr());
+        let (tx, rx) = channel();
+        let thread = thread::spawn(move || run_ws(listener.accept().unwrap().0, &rx));
+        Server {
+            messages: Some(tx),
+            addr: Addr::Tcp(addr),
+            thread: Some(thread),
+        }
+    }
+
     pub fn receive(&self, msg: &str) {
         self.msg(Message::Read(self.replace_port(msg)));
     }
@@ -178,6 +366,13 @@ impl Server {
         self.msg(Message::Write(self.replace_port(msg)));
     }
 
+    // Queues a frame for a WebSocket server to send right after the
+    // handshake. `head` is the first byte of the frame, holding the FIN bit
+    // and the opcode.
+    pub fn send_frame(&self, head: u8, payload: &[u8]) {
+        self.msg(Message::Frame(head, payload.to_vec()));
+    }
+
     fn msg(&self, msg: Message) {
         t!(self.messages.as_ref().unwrap().send(msg));
     }
@@ -203,6 +398,10 @@ impl Server {
             Addr::Unix(_) => format!("http://localhost{}", path),
         }
     }
+
+    pub fn ws_url(&self, path: &str) -> String {
+        format!("ws://{}{}", self.addr(), path)
+    }
 }
 
 impl Drop for Server {
@@ -0,0 +1,170 @@
+#![cfg(feature = "ws_7_86_0")]
+
+use std::thread;
+use std::time::Duration;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+use curl::easy::{Easy, Easy2, Handler, WebSocket, WriteError, WsFlags, WsFrame};
+use curl::Error;
+
+use crate::server::Server;
+mod server;
+
+struct Nothing;
+
+impl Handler for Nothing {}
+
+#[derive(Default)]
+struct Frames(Vec<(WsFrame, Vec<u8>)>);
+
+impl Handler for Frames {
+    fn ws_write(&mut self, data: &[u8], frame: &WsFrame) -> Result<usize, WriteError> {
+        self.0.push((*frame, data.to_vec()));
+        Ok(data.len())
+    }
+}
+
+fn connect(s: &Server) -> WebSocket<Nothing> {
+    let mut easy = Easy2::new(Nothing);
+    t!(easy.url(&s.ws_url("/")));
+    t!(easy.timeout(Duration::new(20, 0)));
+    t!(WebSocket::connect(easy))
+}
+
+// The connection is non-blocking, so keep trying until something arrives.
+fn recv<H>(ws: &mut WebSocket<H>, buf: &mut [u8]) -> (usize, WsFrame) {
+    for _ in 0..2000 {
+        match ws.recv(buf) {
+            Err(ref e) if e.is_again() => thread::sleep(Duration::from_millis(10)),
+            res => return t!(res as Result<_, Error>),
+        }
+    }
+    panic!("timed out waiting for a frame")
+}
+
+#[test]
+fn echo_text_and_binary() {
+    let s = Server::new_ws();
+    let mut ws = connect(&s);
+    let mut buf = [0; 64];
+
+    assert_eq!(t!(ws.send_text("hello")), 5);
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], b"hello");
+    assert!(frame.is_text());
+    assert!(!frame.is_binary());
+    assert_eq!(frame.len(), 5);
+    assert_eq!(frame.bytes_left(), 0);
+
+    assert_eq!(t!(ws.send_binary(&[0, 1, 2, 255])), 4);
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], &[0, 1, 2, 255]);
+    assert!(frame.is_binary());
+    assert!(!frame.is_text());
+}
+
+#[test]
+fn partial_frames() {
+    let s = Server::new_ws();
+    let mut ws = connect(&s);
+
+    t!(ws.send_binary(b"0123456789"));
+    let mut buf = [0; 4];
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], b"0123");
+    assert_eq!(frame.offset(), 0);
+    assert_eq!(frame.bytes_left(), 6);
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], b"4567");
+    assert_eq!(frame.offset(), 4);
+    assert_eq!(frame.bytes_left(), 2);
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], b"89");
+    assert_eq!(frame.offset(), 8);
+    assert_eq!(frame.bytes_left(), 0);
+}
+
+#[test]
+fn fragmented_message() {
+    let s = Server::new_ws();
+    let mut ws = connect(&s);
+    let mut buf = [0; 64];
+
+    t!(ws.send(b"hel", WsFlags::new().text(true).continued(true)));
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], b"hel");
+    assert!(frame.is_text());
+    assert!(frame.is_continued());
+
+    t!(ws.send(b"lo", WsFlags::new().text(true)));
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert_eq!(&buf[..n], b"lo");
+    assert!(!frame.is_continued());
+}
+
+#[test]
+fn ping_and_close() {
+    let s = Server::new_ws();
+    let mut ws = connect(&s);
+    let mut buf = [0; 64];
+
+    t!(ws.ping(b"are you there"));
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert!(frame.is_pong());
+    assert_eq!(&buf[..n], b"are you there");
+
+    t!(ws.close(1000, "bye"));
+    let (n, frame) = recv(&mut ws, &mut buf);
+    assert!(frame.is_close());
+    assert_eq!(&buf[..n], b"\x03\xe8bye");
+}
+
+#[test]
+fn callback_mode() {
+    let s = Server::new_ws();
+    s.send_frame(0x81, b"hello");
+    s.send_frame(0x82, &[1, 2, 3]);
+    s.send_frame(0x88, &1000u16.to_be_bytes());
+
+    let mut easy = Easy2::new(Frames::default());
+    t!(easy.url(&s.ws_url("/")));
+    t!(easy.timeout(Duration::new(20, 0)));
+    t!(easy.perform());
+
+    let frames = &easy.get_ref().0;
+    assert_eq!(frames.len(), 3);
+    assert!(frames[0].0.is_text());
+    assert_eq!(frames[0].1, b"hello");
+    assert!(frames[1].0.is_binary());
+    assert_eq!(frames[1].1, [1, 2, 3]);
+    assert!(frames[2].0.is_close());
+}
+
+#[test]
+fn callback_mode_defaults_to_write() {
+    let s = Server::new_ws();
+    s.send_frame(0x81, b"hello");
+    s.send_frame(0x88, &1000u16.to_be_bytes());
+
+    let mut data = Vec::new();
+    let mut easy = Easy::new();
+    t!(easy.url(&s.ws_url("/")));
+    t!(easy.timeout(Duration::new(20, 0)));
+    {
+        let mut transfer = easy.transfer();
+        t!(transfer.write_function(|d| {
+            data.extend_from_slice(d);
+            Ok(d.len())
+        }));
+        t!(transfer.perform());
+    }
+    assert_eq!(data, b"hello\x03\xe8");
+}